@@ -0,0 +1,64 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::ParseError;
+
+#[derive(Debug)]
+pub enum Error {
+    /// No input file was given on the command line.
+    MissingInputPath,
+    /// The input file could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// Part of the input couldn't be parsed.
+    Parse(ParseError),
+    /// The input doesn't have the shape the puzzle describes.
+    InvalidInput(String),
+}
+
+impl Error {
+    /// Records which file the input came from, for errors that point into it.
+    pub fn with_file(self, path: impl AsRef<Path>) -> Self {
+        match self {
+            Error::Parse(error) => Error::Parse(error.with_file(path)),
+            other => other,
+        }
+    }
+
+    /// Renders the error for the terminal. Parse errors show the offending line.
+    pub fn render(&self) -> String {
+        match self {
+            Error::Parse(error) => error.render(),
+            other => format!("error: {}", other),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingInputPath => write!(f, "no input file given"),
+            Error::Io { path, source } => {
+                write!(f, "could not read {}: {}", path.display(), source)
+            }
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
@@ -0,0 +1,21 @@
+use std::path::{Path, PathBuf};
+
+use crate::Error;
+
+/// Returns the input path passed as the first command line argument.
+pub fn input_path_from_args() -> Result<PathBuf, Error> {
+    std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .ok_or(Error::MissingInputPath)
+}
+
+/// Reads the whole input file into a string.
+pub fn read_input(path: impl AsRef<Path>) -> Result<String, Error> {
+    let path = path.as_ref();
+
+    std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
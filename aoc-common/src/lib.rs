@@ -0,0 +1,19 @@
+//! Helpers shared by every day of the puzzle solutions: the [`Solution`] trait each day implements,
+//! loading the input file, splitting it into lines and sections, parsing them with errors that
+//! point at the offending text, opt-in logging, and a seeded [`Rng`] for generating inputs.
+
+mod error;
+mod input;
+mod logging;
+mod parse_error;
+mod rng;
+mod solution;
+mod split;
+
+pub use error::Error;
+pub use input::{input_path_from_args, read_input};
+pub use logging::{init_logging, LOG_ENV};
+pub use parse_error::ParseError;
+pub use rng::Rng;
+pub use solution::{solve_from_args, Part, Solution};
+pub use split::{lines, sections, Line};
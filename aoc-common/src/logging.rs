@@ -0,0 +1,49 @@
+use std::io::IsTerminal;
+
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// The environment variable that turns logging on, with a filter such as `debug` or
+/// `day5=trace`.
+pub const LOG_ENV: &str = "AOC_LOG";
+
+/// Sends log messages to stderr when they are asked for, either with `verbose` or through
+/// [`LOG_ENV`]. Without either, nothing is logged and only the answers are printed.
+pub fn init_logging(verbose: bool) {
+    let Some(filter) = filter(verbose, std::env::var(LOG_ENV).ok().as_deref()) else {
+        return;
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .init();
+}
+
+/// The filter from the environment wins over `verbose`, so it can narrow things down further.
+fn filter(verbose: bool, env: Option<&str>) -> Option<EnvFilter> {
+    match env {
+        Some(directives) if !directives.is_empty() => Some(EnvFilter::new(directives)),
+        _ if verbose => Some(EnvFilter::new("debug")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_off_by_default() {
+        assert!(filter(false, None).is_none());
+        assert!(filter(false, Some("")).is_none());
+    }
+
+    #[test]
+    fn test_env_wins_over_verbose() {
+        let filter = filter(true, Some("day5=trace")).unwrap();
+        assert_eq!("day5=trace", filter.to_string());
+    }
+}
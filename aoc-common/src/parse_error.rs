@@ -0,0 +1,178 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Something in the input that couldn't be parsed, and where exactly it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The file the input was read from, once it is known.
+    pub file: Option<PathBuf>,
+    /// The line the problem is on, starting at 1.
+    pub line: usize,
+    /// The character the problem starts at, starting at 1.
+    pub column: usize,
+    /// The offending text. Empty when something is missing rather than wrong.
+    pub snippet: String,
+    /// The whole line the problem is on.
+    pub line_text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error for `fragment`, which must be a slice of `line_text`.
+    pub fn in_line(
+        line: usize,
+        line_text: &str,
+        fragment: &str,
+        message: impl Into<String>,
+    ) -> ParseError {
+        let offset = offset_in(line_text, fragment);
+
+        ParseError {
+            file: None,
+            line,
+            column: line_text[..offset].chars().count() + 1,
+            snippet: fragment.to_string(),
+            line_text: line_text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error for the `len` bytes starting at byte `offset` of the whole input.
+    pub fn at_offset(input: &str, offset: usize, len: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |index| offset + index);
+        let line_text = input[line_start..line_end].trim_end_matches('\r');
+
+        let end = (offset + len).min(line_start + line_text.len()).max(offset);
+        let line = input[..line_start].matches('\n').count() + 1;
+
+        Self::in_line(line, line_text, &input[offset..end], message)
+    }
+
+    pub fn with_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Renders the error with the offending line and a caret underneath the problem.
+    pub fn render(&self) -> String {
+        let location = match &self.file {
+            Some(file) => format!("{}:{}:{}", file.display(), self.line, self.column),
+            None => format!("{}:{}", self.line, self.column),
+        };
+
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let carets = "^".repeat(self.snippet.width().max(1));
+
+        // Lines the caret up with the problem however the line is displayed: wide characters
+        // take up two columns, and tabs are kept as they are so they line up the same way.
+        let indent: String = self
+            .line_text
+            .chars()
+            .take(self.column - 1)
+            .map(|c| match c {
+                '\t' => "\t".to_string(),
+                _ => " ".repeat(c.width().unwrap_or(0)),
+            })
+            .collect();
+
+        format!(
+            "error: {}\n{}--> {}\n{} |\n{} | {}\n{} | {}{}",
+            self.message,
+            gutter,
+            location,
+            gutter,
+            line_number,
+            self.line_text,
+            gutter,
+            indent,
+            carets
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Finds where `fragment` starts inside `text`. Fragments that aren't slices of `text` are
+/// reported at its end.
+fn offset_in(text: &str, fragment: &str) -> usize {
+    let start = text.as_ptr() as usize;
+    let position = fragment.as_ptr() as usize;
+
+    if position >= start && position + fragment.len() <= start + text.len() {
+        position - start
+    } else {
+        text.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_line() {
+        let line = "Game 3: x blue";
+        let error = ParseError::in_line(3, line, &line[8..9], "expected a count");
+
+        assert_eq!(3, error.line);
+        assert_eq!(9, error.column);
+        assert_eq!("x", error.snippet);
+    }
+
+    #[test]
+    fn test_at_offset() {
+        let input = "467..114..\n...*......\n..35..6x3.\n";
+        let error = ParseError::at_offset(input, 29, 1, "unexpected character");
+
+        assert_eq!(3, error.line);
+        assert_eq!(8, error.column);
+        assert_eq!("..35..6x3.", error.line_text);
+    }
+
+    #[test]
+    fn test_render() {
+        let line = "32T3Z 765";
+        let error = ParseError::in_line(12, line, &line[4..5], "'Z' is not a card")
+            .with_file("day7/input/puzzle.txt");
+
+        assert_eq!(
+            "error: 'Z' is not a card\n  --> day7/input/puzzle.txt:12:5\n   |\n12 | 32T3Z 765\n   |     ^",
+            error.render()
+        );
+    }
+
+    #[test]
+    fn test_render_missing_text() {
+        let line = "Card 1: 41 48";
+        let error = ParseError::in_line(1, line, &line[line.len()..], "expected '|'");
+
+        let expected = format!("1 | Card 1: 41 48\n  | {}^", " ".repeat(line.len()));
+        assert!(error.render().ends_with(&expected));
+    }
+
+    #[test]
+    fn test_render_wide_characters() {
+        let line = "１２x\t3 é";
+        let error = ParseError::in_line(1, line, &line[8..], "unexpected text");
+
+        assert_eq!(5, error.column);
+        assert!(error.render().ends_with("1 | １２x\t3 é\n  |      \t^^^"));
+    }
+}
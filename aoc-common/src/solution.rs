@@ -0,0 +1,85 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::process::ExitCode;
+
+use tracing::{debug, info_span};
+
+use crate::Error;
+
+/// One of the two halves of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// The part's number, as the puzzle counts them.
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A day's puzzle. The input is parsed once and can then be used to solve either part.
+pub trait Solution {
+    /// The day of December the puzzle was released on.
+    const DAY: u8;
+
+    /// The parsed puzzle input, shared by both parts.
+    type Input;
+
+    /// The answer to either part.
+    type Answer: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer, Error>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer, Error>;
+
+    fn solve(input: &Self::Input, part: Part) -> Result<Self::Answer, Error> {
+        match part {
+            Part::One => Self::part1(input),
+            Part::Two => Self::part2(input),
+        }
+    }
+}
+
+/// Solves both parts for the input file named on the command line and prints the answers.
+/// Logging is turned on with [`LOG_ENV`](crate::LOG_ENV).
+pub fn solve_from_args<S: Solution>() -> ExitCode {
+    crate::init_logging(false);
+
+    let result = crate::input_path_from_args().and_then(|path| solve_file::<S>(&path));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error.render());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Solves both parts for the input file at `path` and prints the answers.
+fn solve_file<S: Solution>(path: &Path) -> Result<(), Error> {
+    let _day = info_span!("day", day = S::DAY).entered();
+
+    debug!(path = %path.display(), "reading input");
+
+    let input = crate::read_input(path)?;
+    let input = info_span!("parse")
+        .in_scope(|| S::parse(&input))
+        .map_err(|error| error.with_file(path))?;
+
+    for part in [Part::One, Part::Two] {
+        let answer =
+            info_span!("solve", part = part.number()).in_scope(|| S::solve(&input, part))?;
+        println!("Part {}: {}", part.number(), answer);
+    }
+
+    Ok(())
+}
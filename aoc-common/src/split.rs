@@ -0,0 +1,148 @@
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// A line of the input, along with its line number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// The line number, starting at 1.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Creates an error for `fragment`, which must be a slice of this line.
+    pub fn error(&self, fragment: &str, message: impl Into<String>) -> ParseError {
+        ParseError::in_line(self.number, self.text, fragment, message)
+    }
+
+    /// The empty slice at the end of the line, for errors about something that is missing.
+    pub fn end(&self) -> &'a str {
+        &self.text[self.text.len()..]
+    }
+
+    /// Parses `fragment`, a slice of this line. `expected` describes what it should have been.
+    pub fn parse<T: FromStr>(&self, fragment: &str, expected: &str) -> Result<T, ParseError> {
+        fragment
+            .parse::<T>()
+            .map_err(|_| self.error(fragment, format!("expected {}", expected)))
+    }
+
+    /// Parses every whitespace separated word of `fragment`, a slice of this line, as an integer.
+    pub fn ints<T: FromStr>(&self, fragment: &str) -> Result<Vec<T>, ParseError> {
+        fragment
+            .split_whitespace()
+            .map(|word| self.parse(word, "an integer"))
+            .collect()
+    }
+}
+
+/// Iterates over the non-empty lines of the input.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line {
+            number: index + 1,
+            text,
+        })
+        .filter(|line| !line.text.trim().is_empty())
+}
+
+/// Iterates over the blank-line separated sections of the input, each as its lines.
+pub fn sections(input: &str) -> impl Iterator<Item = Vec<Line<'_>>> {
+    let mut lines = input.lines().enumerate().map(|(index, text)| Line {
+        number: index + 1,
+        text,
+    });
+
+    std::iter::from_fn(move || {
+        let mut section = vec![];
+
+        // A section ends at the first line that only holds whitespace.
+        for line in lines.by_ref() {
+            if !line.text.trim().is_empty() {
+                section.push(line);
+            } else if !section.is_empty() {
+                break;
+            }
+        }
+
+        (!section.is_empty()).then_some(section)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_skips_blank_lines() {
+        let input = "a\n\nb\n   \nc\n";
+
+        assert_eq!(
+            vec![(1, "a"), (3, "b"), (5, "c")],
+            lines(input)
+                .map(|line| (line.number, line.text))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "seeds: 1 2\n\nfirst map:\n1 2 3\n\n\nsecond map:\n4 5 6\n";
+
+        let sections: Vec<Vec<(usize, &str)>> = sections(input)
+            .map(|section| {
+                section
+                    .iter()
+                    .map(|line| (line.number, line.text))
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                vec![(1, "seeds: 1 2")],
+                vec![(3, "first map:"), (4, "1 2 3")],
+                vec![(7, "second map:"), (8, "4 5 6")],
+            ],
+            sections
+        );
+    }
+
+    #[test]
+    fn test_sections_crlf() {
+        let input = "a\r\nb\r\n\r\nc\r\n";
+        let sections: Vec<usize> = sections(input).map(|section| section.len()).collect();
+
+        assert_eq!(vec![2, 1], sections);
+    }
+
+    #[test]
+    fn test_ints() {
+        let line = Line {
+            number: 1,
+            text: "Card 1: 83 86  6 31",
+        };
+
+        assert_eq!(
+            vec![83, 86, 6, 31],
+            line.ints::<u64>(&line.text[7..]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ints_invalid() {
+        let line = Line {
+            number: 4,
+            text: "1 two 3",
+        };
+        let error = line.ints::<u64>(line.text).unwrap_err();
+
+        assert_eq!(
+            (4, 3, "two"),
+            (error.line, error.column, error.snippet.as_str())
+        );
+    }
+}
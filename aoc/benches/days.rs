@@ -0,0 +1,188 @@
+//! Times parsing and each part separately for every day, on the puzzle inputs and on scaled-up
+//! copies of them, and writes the results to a JSON file so runs can be compared. Day 6 can't be
+//! copied, so its scaled-up input is generated races that glue into a longer one.
+//!
+//! Run with `cargo bench -p aoc`. The results go to `target/aoc-bench.json`, or to the path in
+//! `AOC_BENCH_OUTPUT`.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc::{Day, DAYS};
+use aoc_common::Part;
+use serde::{Serialize, Serializer};
+
+/// How many times bigger the scaled-up inputs are.
+const SCALE: usize = 10;
+
+/// Every input is run at least this many times...
+const MIN_ITERATIONS: usize = 3;
+
+/// ...and then until this much time has been spent on it, up to `MAX_ITERATIONS`.
+const TARGET_TIME: Duration = Duration::from_secs(2);
+const MAX_ITERATIONS: usize = 100;
+
+#[derive(Serialize)]
+struct Measurement {
+    day: u8,
+    input: String,
+    bytes: usize,
+    iterations: usize,
+    #[serde(rename = "parse_ns", serialize_with = "nanos")]
+    parse: Duration,
+    #[serde(rename = "part1_ns", serialize_with = "nanos")]
+    part1: Duration,
+    #[serde(rename = "part2_ns", serialize_with = "nanos")]
+    part2: Duration,
+}
+
+/// Writes a duration as a whole number of nanoseconds.
+fn nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_nanos())
+}
+
+fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+/// Gives every line a new number, counting up from 1, after `prefix`.
+fn renumber(lines: impl Iterator<Item = String>, prefix: &str) -> String {
+    let mut output = String::new();
+
+    for (index, line) in lines.enumerate() {
+        let (_, rest) = line.split_once(':').unwrap();
+        writeln!(output, "{} {}:{}", prefix, index + 1, rest).unwrap();
+    }
+
+    output
+}
+
+/// Makes `input` `factor` times bigger, keeping it valid for the day. `None` if `input` isn't.
+fn scale(day: &Day, input: &str, factor: usize) -> Option<String> {
+    let lines = || {
+        (0..factor).flat_map(|_| {
+            input
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+        })
+    };
+
+    match day.number {
+        1 | 3 | 7 => Some(lines().map(|line| line + "\n").collect()),
+        2 => Some(renumber(lines(), "Game")),
+        4 => Some(renumber(lines(), "Card")),
+        5 => {
+            let (seeds, maps) = input.split_once('\n')?;
+            let seeds = seeds.strip_prefix("seeds:")?;
+
+            Some(format!("seeds:{}\n{}", seeds.repeat(factor), maps))
+        }
+        // Part two glues every race into one, so more races would overflow. Instead, generate
+        // races that glue into one `factor` times longer.
+        6 => {
+            let races = day6::read_races(input).ok()?;
+            let time = day6::merge_races(&races).ok()?.time();
+
+            Some(day.generate(time as usize * factor, 1))
+        }
+        _ => None,
+    }
+}
+
+fn measure(day: &Day, name: &str, input: &str) -> Measurement {
+    let parts = [Part::One, Part::Two];
+
+    let mut parse_times = vec![];
+    let mut part_times: Vec<Vec<Duration>> = vec![vec![]; parts.len()];
+
+    let start = Instant::now();
+
+    while parse_times.len() < MIN_ITERATIONS
+        || (start.elapsed() < TARGET_TIME && parse_times.len() < MAX_ITERATIONS)
+    {
+        let report = day.run(input, &parts).unwrap_or_else(|error| {
+            panic!("day {} {}: {}", day.number, name, error);
+        });
+
+        parse_times.push(report.parse_time);
+
+        for (times, part) in part_times.iter_mut().zip(&report.parts) {
+            times.push(part.solve_time);
+        }
+    }
+
+    Measurement {
+        day: day.number,
+        input: name.to_string(),
+        bytes: input.len(),
+        iterations: parse_times.len(),
+        parse: median(&mut parse_times),
+        part1: median(&mut part_times[0]),
+        part2: median(&mut part_times[1]),
+    }
+}
+
+fn median(times: &mut [Duration]) -> Duration {
+    times.sort();
+    times[times.len() / 2]
+}
+
+fn main() {
+    let root = repo_root();
+    let mut measurements = vec![];
+
+    for day in DAYS {
+        let path = day.puzzle_path();
+
+        let Ok(puzzle) = aoc_common::read_input(&path) else {
+            eprintln!("Day {}: skipped, {} is missing", day.number, path.display());
+            continue;
+        };
+
+        let mut inputs = vec![("puzzle".to_string(), puzzle.clone())];
+
+        if let Some(scaled) = scale(day, &puzzle, SCALE) {
+            inputs.push((format!("puzzle-x{}", SCALE), scaled));
+        }
+
+        for (name, input) in inputs {
+            let measurement = measure(day, &name, &input);
+
+            println!(
+                "Day {} {:<12} parse {:>12?}  part 1 {:>12?}  part 2 {:>12?}  ({} runs)",
+                day.number,
+                name,
+                measurement.parse,
+                measurement.part1,
+                measurement.part2,
+                measurement.iterations
+            );
+
+            measurements.push(measurement);
+        }
+    }
+
+    // The brute force is far too slow for the puzzle input, but the example is enough to see what
+    // a thread per seed pair costs.
+    let example = aoc_common::read_input(root.join("day5/input/example.txt")).unwrap();
+    let almanac = day5::Almanac::parse(&example).unwrap();
+
+    let start = Instant::now();
+    day5::min_location_brute_force(&almanac);
+    println!("Day 5 brute force on the example: {:?}", start.elapsed());
+
+    let output = std::env::var_os("AOC_BENCH_OUTPUT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join("target/aoc-bench.json"));
+
+    let file = BufWriter::new(File::create(&output).unwrap());
+    serde_json::to_writer_pretty(file, &measurements).unwrap();
+    println!("Results written to {}", output.display());
+}
@@ -0,0 +1,128 @@
+//! Records for `--format json`, one JSON object per line, so results can be ingested without
+//! scraping the text output.
+
+use std::path::Path;
+
+use aoc_common::Error;
+use serde::Serialize;
+
+use crate::Report;
+
+/// The answer to one part of a day.
+#[derive(Debug, Serialize)]
+pub struct AnswerRecord {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+    pub input_path: String,
+}
+
+/// A day that couldn't be run, and where in the input the problem is when that is known.
+#[derive(Debug, Serialize)]
+pub struct ErrorRecord {
+    pub day: u8,
+    pub input_path: String,
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+impl AnswerRecord {
+    /// One record for each part in the report. Every record carries the time taken to parse.
+    pub fn from_report(report: &Report, input_path: &Path) -> Vec<AnswerRecord> {
+        report
+            .parts
+            .iter()
+            .map(|part| AnswerRecord {
+                day: report.day,
+                part: part.part.number(),
+                answer: part.answer.clone(),
+                parse_ms: report.parse_time.as_secs_f64() * 1000.0,
+                solve_ms: part.solve_time.as_secs_f64() * 1000.0,
+                input_path: input_path.display().to_string(),
+            })
+            .collect()
+    }
+}
+
+impl ErrorRecord {
+    pub fn new(day: u8, input_path: &Path, error: &Error) -> ErrorRecord {
+        let (message, location) = match error {
+            Error::Parse(error) => (error.message.clone(), Some(error)),
+            other => (other.to_string(), None),
+        };
+
+        ErrorRecord {
+            day,
+            input_path: input_path.display().to_string(),
+            error: message,
+            line: location.map(|error| error.line),
+            column: location.map(|error| error.column),
+            snippet: location.map(|error| error.snippet.clone()),
+        }
+    }
+}
+
+/// Formats a record as a single line of JSON.
+pub fn to_line(record: &impl Serialize) -> String {
+    serde_json::to_string(record).expect("records always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_common::{ParseError, Part};
+
+    use super::*;
+    use crate::PartReport;
+
+    #[test]
+    fn test_answer_record() {
+        let report = Report {
+            day: 6,
+            parse_time: Duration::from_micros(1500),
+            parts: vec![PartReport {
+                part: Part::Two,
+                answer: "71503".to_string(),
+                solve_time: Duration::from_millis(2),
+            }],
+        };
+
+        let records = AnswerRecord::from_report(&report, Path::new("day6/input/example.txt"));
+
+        assert_eq!(
+            r#"{"day":6,"part":2,"answer":"71503","parse_ms":1.5,"solve_ms":2.0,"input_path":"day6/input/example.txt"}"#,
+            to_line(&records[0])
+        );
+    }
+
+    #[test]
+    fn test_error_record() {
+        let line = "32T3Z 765";
+        let error = Error::Parse(ParseError::in_line(
+            4,
+            line,
+            &line[4..5],
+            "'Z' is not a card",
+        ));
+
+        assert_eq!(
+            r#"{"day":7,"input_path":"hands.txt","error":"'Z' is not a card","line":4,"column":5,"snippet":"Z"}"#,
+            to_line(&ErrorRecord::new(7, Path::new("hands.txt"), &error))
+        );
+
+        let error = Error::InvalidInput("there are no races".to_string());
+
+        assert_eq!(
+            r#"{"day":6,"input_path":"races.txt","error":"invalid input: there are no races"}"#,
+            to_line(&ErrorRecord::new(6, Path::new("races.txt"), &error))
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Runs and times the solutions for every day.
+
+pub mod json;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_common::{Error, Part, Rng, Solution};
+use tracing::{debug, info_span};
+
+/// The answer to one part, and how long it took to solve.
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: String,
+    pub solve_time: Duration,
+}
+
+/// Everything that happened when running a day on an input.
+#[derive(Debug)]
+pub struct Report {
+    pub day: u8,
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+/// A day that can be run on any input.
+pub struct Day {
+    pub number: u8,
+    run: fn(&str, &[Part]) -> Result<Report, Error>,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Day {
+    /// Parses the input once, then solves the requested parts in order.
+    pub fn run(&self, input: &str, parts: &[Part]) -> Result<Report, Error> {
+        (self.run)(input, parts)
+    }
+
+    /// Generates an input of roughly `size`, in whatever unit suits the day. The same seed always
+    /// gives the same input.
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+
+    /// Where the day's puzzle input lives in the repository this was built from, wherever it is
+    /// run from.
+    pub fn puzzle_path(&self) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join(format!("day{}/input/puzzle.txt", self.number))
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        run: run::<day1::Day1>,
+        generate: day1::generate::generate,
+    },
+    Day {
+        number: 2,
+        run: run::<day2::Day2>,
+        generate: day2::generate::generate,
+    },
+    Day {
+        number: 3,
+        run: run::<day3::Day3>,
+        generate: day3::generate::generate,
+    },
+    Day {
+        number: 4,
+        run: run::<day4::Day4>,
+        generate: day4::generate::generate,
+    },
+    Day {
+        number: 5,
+        run: run::<day5::Day5>,
+        generate: day5::generate::generate,
+    },
+    Day {
+        number: 6,
+        run: run::<day6::Day6>,
+        generate: day6::generate::generate,
+    },
+    Day {
+        number: 7,
+        run: run::<day7::Day7>,
+        generate: day7::generate::generate,
+    },
+];
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Report, Error> {
+    let _day = info_span!("day", day = S::DAY).entered();
+
+    let start = Instant::now();
+    let parsed = info_span!("parse").in_scope(|| S::parse(input))?;
+    let parse_time = start.elapsed();
+
+    debug!(bytes = input.len(), ?parse_time, "parsed input");
+
+    let mut reports = vec![];
+
+    for part in parts {
+        let start = Instant::now();
+        let answer =
+            info_span!("solve", part = part.number()).in_scope(|| S::solve(&parsed, *part))?;
+        let solve_time = start.elapsed();
+
+        debug!(part = part.number(), %answer, ?solve_time, "solved");
+
+        reports.push(PartReport {
+            part: *part,
+            answer: answer.to_string(),
+            solve_time,
+        });
+    }
+
+    Ok(Report {
+        day: S::DAY,
+        parse_time,
+        parts: reports,
+    })
+}
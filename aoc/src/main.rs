@@ -0,0 +1,214 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc::json::{self, AnswerRecord, ErrorRecord};
+use aoc::{find_day, Day, Report, DAYS};
+use aoc_common::Part;
+
+const USAGE: &str = "\
+usage: aoc run --day <N> [--part <1|2>] [--input <PATH>] [--format <text|json>] [--verbose]
+       aoc run --all [--part <1|2>] [--format <text|json>] [--verbose]
+       aoc gen --day <N> --size <SIZE> [--seed <SEED>]
+
+Without --input, a day runs on dayN/input/puzzle.txt in the repository it was built from.
+--format json prints one JSON object per answer or error instead of text.
+--verbose logs debug messages to stderr. AOC_LOG=<filter>, like AOC_LOG=day5=trace, picks
+exactly what to log.
+gen prints a generated input for the day. The same seed always gives the same input.";
+
+enum Command {
+    Run(RunArgs),
+    Gen(GenArgs),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+struct RunArgs {
+    days: Vec<&'static Day>,
+    parts: Vec<Part>,
+    input: Option<PathBuf>,
+    format: Format,
+    verbose: bool,
+}
+
+struct GenArgs {
+    day: &'static Day,
+    size: usize,
+    seed: u64,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => Ok(Command::Run(parse_run_args(args)?)),
+        Some("gen") => Ok(Command::Gen(parse_gen_args(args)?)),
+        Some(command) => Err(format!("unknown command '{}'", command)),
+        None => Err("no command given".to_string()),
+    }
+}
+
+fn parse_day(value: &str) -> Result<&'static Day, String> {
+    value
+        .parse::<u8>()
+        .ok()
+        .and_then(find_day)
+        .ok_or(format!("there is no day '{}'", value))
+}
+
+fn parse_run_args(mut args: impl Iterator<Item = String>) -> Result<RunArgs, String> {
+    let mut day: Option<&'static Day> = None;
+    let mut all = false;
+    let mut parts = vec![Part::One, Part::Two];
+    let mut input: Option<PathBuf> = None;
+    let mut format = Format::Text;
+    let mut verbose = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(&value()?)?),
+            "--part" => {
+                parts = match value()?.as_str() {
+                    "1" => vec![Part::One],
+                    "2" => vec![Part::Two],
+                    other => return Err(format!("there is no part '{}'", other)),
+                };
+            }
+            "--input" => input = Some(PathBuf::from(value()?)),
+            "--format" => {
+                format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    other => return Err(format!("there is no format '{}'", other)),
+                };
+            }
+            "--all" => all = true,
+            "--verbose" | "-v" => verbose = true,
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    let days = match (day, all) {
+        (Some(day), false) => vec![day],
+        (None, true) if input.is_none() => DAYS.iter().collect(),
+        (None, true) => return Err("--input can't be used with --all".to_string()),
+        (Some(_), true) => return Err("--day can't be used with --all".to_string()),
+        (None, false) => return Err("either --day or --all is needed".to_string()),
+    };
+
+    Ok(RunArgs {
+        days,
+        parts,
+        input,
+        format,
+        verbose,
+    })
+}
+
+fn parse_gen_args(mut args: impl Iterator<Item = String>) -> Result<GenArgs, String> {
+    let mut day: Option<&'static Day> = None;
+    let mut size: Option<usize> = None;
+    let mut seed = 0;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(&value()?)?),
+            "--size" => {
+                let value = value()?;
+                size = Some(
+                    value
+                        .parse()
+                        .or(Err(format!("'{}' is not a size", value)))?,
+                );
+            }
+            "--seed" => {
+                let value = value()?;
+                seed = value
+                    .parse()
+                    .or(Err(format!("'{}' is not a seed", value)))?;
+            }
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(GenArgs {
+        day: day.ok_or("--day is needed")?,
+        size: size.ok_or("--size is needed")?,
+        seed,
+    })
+}
+
+fn print_report(report: &Report) {
+    println!("  Parsed in {:?}", report.parse_time);
+
+    for part in &report.parts {
+        println!(
+            "  Part {}: {} ({:?})",
+            part.part.number(),
+            part.answer,
+            part.solve_time
+        );
+    }
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    aoc_common::init_logging(args.verbose);
+
+    let mut failed = false;
+
+    for day in args.days {
+        let path = args.input.clone().unwrap_or_else(|| day.puzzle_path());
+
+        if args.format == Format::Text {
+            println!("Day {} ({})", day.number, path.display());
+        }
+
+        let result = aoc_common::read_input(&path).and_then(|input| day.run(&input, &args.parts));
+
+        match (result, args.format) {
+            (Ok(report), Format::Text) => print_report(&report),
+            (Ok(report), Format::Json) => {
+                for record in AnswerRecord::from_report(&report, &path) {
+                    println!("{}", json::to_line(&record));
+                }
+            }
+            (Err(error), Format::Text) => {
+                eprintln!("{}", error.with_file(&path).render());
+                failed = true;
+            }
+            (Err(error), Format::Json) => {
+                println!(
+                    "{}",
+                    json::to_line(&ErrorRecord::new(day.number, &path, &error))
+                );
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => run(args),
+        Ok(Command::Gen(args)) => {
+            print!("{}", args.day.generate(args.size, args.seed));
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Runs every day against every file in its `input/` directory and checks the answers against the
+//! day's `answers.toml`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc::{Day, DAYS};
+use aoc_common::Part;
+
+fn day_dir(day: &Day) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join(format!("day{}", day.number))
+}
+
+fn input_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir.join("input"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+
+    files.sort();
+    files
+}
+
+/// Checks one input file against its table in the manifest, returning a description of every
+/// problem found.
+fn check_input(day: &Day, path: &Path, expected: Option<&toml::Value>) -> Vec<String> {
+    let name = path.file_stem().unwrap().to_string_lossy();
+
+    let Some(expected) = expected else {
+        return vec![format!(
+            "day {}: no [{}] table in answers.toml",
+            day.number, name
+        )];
+    };
+
+    let input = aoc_common::read_input(path).unwrap();
+    let report = match day.run(&input, &[Part::One, Part::Two]) {
+        Ok(report) => report,
+        Err(error) => return vec![format!("day {} {}: {}", day.number, name, error)],
+    };
+
+    let mut problems = vec![];
+
+    for (key, part) in ["part1", "part2"].iter().zip(&report.parts) {
+        let expected = match expected.get(key) {
+            Some(toml::Value::String(answer)) => answer.clone(),
+            Some(toml::Value::Integer(answer)) => answer.to_string(),
+            Some(other) => {
+                problems.push(format!(
+                    "day {} {} {}: expected answer {} is neither a string nor an integer",
+                    day.number, name, key, other
+                ));
+                continue;
+            }
+            None => {
+                problems.push(format!(
+                    "day {} {} {}: missing expected answer",
+                    day.number, name, key
+                ));
+                continue;
+            }
+        };
+
+        if part.answer != expected {
+            problems.push(format!(
+                "day {} {} {}: expected {}, got {}",
+                day.number, name, key, expected, part.answer
+            ));
+        }
+    }
+
+    problems
+}
+
+#[test]
+fn test_answers() {
+    let mut problems = vec![];
+
+    for day in DAYS {
+        let dir = day_dir(day);
+
+        let manifest = match fs::read_to_string(dir.join("answers.toml")) {
+            Ok(manifest) => manifest.parse::<toml::Table>().unwrap(),
+            Err(error) => {
+                problems.push(format!("day {}: no answers.toml: {}", day.number, error));
+                continue;
+            }
+        };
+
+        for path in input_files(&dir) {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            problems.extend(check_input(day, &path, manifest.get(&name)));
+        }
+    }
+
+    assert!(problems.is_empty(), "\n{}", problems.join("\n"));
+}
@@ -0,0 +1,30 @@
+//! Checks that every day's generated inputs are accepted by its parser and can be solved.
+
+use aoc::DAYS;
+use aoc_common::Part;
+
+#[test]
+fn test_generated_inputs_solve() {
+    for day in DAYS {
+        for size in [1, 10, 100] {
+            for seed in 0..5 {
+                let input = day.generate(size, seed);
+
+                if let Err(error) = day.run(&input, &[Part::One, Part::Two]) {
+                    panic!(
+                        "day {} size {} seed {}: {}\n{}",
+                        day.number, size, seed, error, input
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_same_seed_same_input() {
+    for day in DAYS {
+        assert_eq!(day.generate(20, 7), day.generate(20, 7));
+        assert_ne!(day.generate(20, 7), day.generate(20, 8));
+    }
+}
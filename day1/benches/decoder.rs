@@ -0,0 +1,123 @@
+//! Compares the automaton decoder against the old approach of checking every spelled digit at
+//! every offset, on a million generated lines, and times decoding them as a stream on one thread
+//! and on every core.
+//!
+//! Run with `cargo bench -p day1-trebuchet`.
+
+use std::hint::black_box;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::Rng;
+use day1::{CalibrationDecoder, Mode, NoDigits, StreamDecoder};
+
+const LINES: usize = 1_000_000;
+const RUNS: usize = 5;
+
+/// The decoder as it was before the automaton: every offset of the line is checked against all
+/// nine words, and every digit found is collected into a string.
+fn starts_with_value(line: &str, spelled_words: bool) -> i64 {
+    let mut num_line = String::new();
+
+    for index in 0..line.len() {
+        let substring_to_end = &line[index..];
+
+        let first_char = substring_to_end.chars().next().unwrap();
+
+        if first_char.is_ascii_digit() {
+            num_line.push(first_char);
+        } else if !spelled_words {
+            continue;
+        } else if substring_to_end.starts_with("one") {
+            num_line.push('1');
+        } else if substring_to_end.starts_with("two") {
+            num_line.push('2');
+        } else if substring_to_end.starts_with("three") {
+            num_line.push('3');
+        } else if substring_to_end.starts_with("four") {
+            num_line.push('4');
+        } else if substring_to_end.starts_with("five") {
+            num_line.push('5');
+        } else if substring_to_end.starts_with("six") {
+            num_line.push('6');
+        } else if substring_to_end.starts_with("seven") {
+            num_line.push('7');
+        } else if substring_to_end.starts_with("eight") {
+            num_line.push('8');
+        } else if substring_to_end.starts_with("nine") {
+            num_line.push('9');
+        }
+    }
+
+    let (Some(first), Some(last)) = (num_line.chars().next(), num_line.chars().next_back()) else {
+        return 0;
+    };
+
+    format!("{}{}", first, last).parse().unwrap()
+}
+
+/// Runs `total` a few times and returns the fastest time along with its answer.
+fn time(total: impl Fn() -> i64) -> (Duration, i64) {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            let answer = black_box(total());
+            (start.elapsed(), answer)
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let input = day1::generate::generate(&mut Rng::new(1), LINES);
+    let lines: Vec<&str> = input.lines().collect();
+
+    println!("{} lines, {} bytes", lines.len(), input.len());
+
+    for mode in [Mode::DigitsOnly, Mode::DigitsAndWords] {
+        let decoder = CalibrationDecoder::new(mode);
+        let spelled_words = mode == Mode::DigitsAndWords;
+
+        let (old_time, old_total) = time(|| {
+            lines
+                .iter()
+                .map(|line| starts_with_value(line, spelled_words))
+                .sum()
+        });
+        let (new_time, new_total) = time(|| decoder.total(lines.iter().copied()));
+
+        assert_eq!(old_total, new_total, "the decoders disagree in {:?}", mode);
+
+        println!(
+            "{:?}: starts_with {:?}, automaton {:?} ({:.1}x)",
+            mode,
+            old_time,
+            new_time,
+            old_time.as_secs_f64() / new_time.as_secs_f64()
+        );
+
+        let cores = thread::available_parallelism().map_or(1, |cores| cores.get());
+
+        for threads in [1, cores] {
+            let stream = StreamDecoder::new(NoDigits::Zero).with_threads(threads);
+            let (stream_time, stream_total) = time(|| {
+                stream
+                    .decode(&decoder, input.as_bytes(), Path::new("generated"))
+                    .unwrap()
+                    .total
+            });
+
+            assert_eq!(
+                new_total, stream_total,
+                "the stream disagrees in {:?}",
+                mode
+            );
+
+            println!(
+                "{:?}: stream on {} thread(s) {:?}",
+                mode, threads, stream_time
+            );
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use aoc_common::{lines, ParseError};
+
+use crate::matcher::{Matcher, ReverseMatcher, Token};
+use crate::Vocabulary;
+
+/// Which tokens on a line count as digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Only `1` to `9`.
+    DigitsOnly,
+    /// `1` to `9`, and the words of a [`Vocabulary`], `one` to `nine` unless another one is
+    /// given.
+    DigitsAndWords,
+    /// `1` to `9`, `one` to `nineteen`, the tens from `twenty` to `ninety`, and hyphenated
+    /// compounds like `twenty-one`, each read as the whole number it stands for.
+    Compound,
+}
+
+/// What to do with a line that has no digits on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoDigits {
+    /// The line is worth 0.
+    #[default]
+    Zero,
+    /// The line is left out, as if it wasn't there.
+    Skip,
+    /// Decoding stops with an error pointing at the line.
+    Error,
+}
+
+/// The calibration values of a whole document, added up.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Calibration {
+    pub total: i64,
+    /// How many lines went into the total.
+    pub lines: usize,
+    /// The numbers of the lines that had no digits, starting at 1.
+    pub without_digits: Vec<usize>,
+}
+
+const DIGITS: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// The digits of East Asian text, which take up as much room as a CJK character.
+const FULL_WIDTH_DIGITS: [&str; 9] = ["１", "２", "３", "４", "５", "６", "７", "８", "９"];
+
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// The number words from `ten` to `ninety-nine`, for [`Mode::Compound`].
+fn compound_words() -> Vec<(String, u8)> {
+    let mut words: Vec<(String, u8)> = TEENS
+        .iter()
+        .map(|word| word.to_string())
+        .zip(10..)
+        .collect();
+
+    for (ten, tens) in TENS.iter().zip((20..).step_by(10)) {
+        words.push((ten.to_string(), tens));
+
+        for (unit, digit) in Vocabulary::english().words() {
+            words.push((format!("{}-{}", ten, unit), tens + digit));
+        }
+    }
+
+    words
+}
+
+/// The number the first and last number on a line make when written one after the other, so
+/// `4` and `7` make 47 and `twenty` and `one` make 201.
+fn combine(first: u8, last: u8) -> i64 {
+    let shift = if last >= 10 { 100 } else { 10 };
+    i64::from(first) * shift + i64::from(last)
+}
+
+/// Recovers the calibration values from the lines of the calibration document.
+///
+/// Lines can hold any UTF-8 text. Anything that isn't a digit or a word of the vocabulary is
+/// skipped over, whatever script it is in.
+#[derive(Debug, Clone)]
+pub struct CalibrationDecoder {
+    mode: Mode,
+    vocabulary: Vocabulary,
+    full_width_digits: bool,
+    forward: Matcher,
+    backward: ReverseMatcher,
+}
+
+impl CalibrationDecoder {
+    pub fn new(mode: Mode) -> Self {
+        match mode {
+            Mode::DigitsOnly => CalibrationDecoder::build(mode, Vocabulary::new(), false),
+            Mode::DigitsAndWords | Mode::Compound => {
+                CalibrationDecoder::build(mode, Vocabulary::english(), false)
+            }
+        }
+    }
+
+    /// A decoder that reads the words of `vocabulary` as digits, as well as the digits
+    /// themselves. Words that overlap, like `dreins` in German, each count.
+    pub fn with_vocabulary(vocabulary: &Vocabulary) -> Self {
+        CalibrationDecoder::build(Mode::DigitsAndWords, vocabulary.clone(), false)
+    }
+
+    /// Also reads full-width digits, like `３`, as digits.
+    pub fn with_full_width_digits(self) -> Self {
+        CalibrationDecoder::build(self.mode, self.vocabulary, true)
+    }
+
+    fn build(mode: Mode, vocabulary: Vocabulary, full_width_digits: bool) -> Self {
+        let full_width: &[&str] = match full_width_digits {
+            true => &FULL_WIDTH_DIGITS,
+            false => &[],
+        };
+
+        let compound = match mode {
+            Mode::Compound => compound_words(),
+            _ => vec![],
+        };
+
+        let patterns = || {
+            DIGITS
+                .iter()
+                .copied()
+                .zip(1..)
+                .chain(full_width.iter().copied().zip(1..))
+                .chain(vocabulary.words())
+                .chain(compound.iter().map(|(word, value)| (word.as_str(), *value)))
+        };
+
+        let forward = Matcher::new(patterns());
+        let backward = ReverseMatcher::new(patterns());
+
+        CalibrationDecoder {
+            mode,
+            vocabulary,
+            full_width_digits,
+            forward,
+            backward,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    pub fn full_width_digits(&self) -> bool {
+        self.full_width_digits
+    }
+
+    /// The number made of the first and last digit on the line, written one after the other. A
+    /// line without any digits is worth nothing.
+    pub fn value(&self, line: &str) -> i64 {
+        match self.first_and_last(line) {
+            Some((first, last)) => combine(first.value, last.value),
+            None => 0,
+        }
+    }
+
+    /// The tokens the calibration value is made of. They are the same token when the line only
+    /// has one.
+    pub fn first_and_last(&self, line: &str) -> Option<(Token, Token)> {
+        // The first digit is searched for from the start and the last one from the end, so
+        // neither search has to look at the middle of a long line.
+        let first = self.forward.find_first(line)?;
+        let last = self.backward.find_last(line).unwrap_or(first);
+
+        Some((first, last))
+    }
+
+    /// Every token on the line, overlapping ones included, in the order they start.
+    pub fn tokens(&self, line: &str) -> Vec<Token> {
+        let mut tokens: Vec<Token> = self.forward.find_iter(line).collect();
+        tokens.sort_by_key(|token| (token.start, std::cmp::Reverse(token.end)));
+        tokens
+    }
+
+    /// The sum of the calibration values of every line.
+    pub fn total<'a>(&self, lines: impl IntoIterator<Item = &'a str>) -> i64 {
+        lines.into_iter().map(|line| self.value(line)).sum()
+    }
+
+    /// Adds up the calibration values of every line of `input`, dealing with lines that have no
+    /// digits as `policy` says. Blank lines are always left out.
+    pub fn decode(&self, input: &str, policy: NoDigits) -> Result<Calibration, ParseError> {
+        let mut calibration = Calibration::default();
+
+        for line in lines(input) {
+            let Some((first, last)) = self.first_and_last(line.text) else {
+                match policy {
+                    NoDigits::Zero => calibration.lines += 1,
+                    NoDigits::Skip => (),
+                    NoDigits::Error => return Err(line.error(line.text, "line has no digits")),
+                }
+
+                calibration.without_digits.push(line.number);
+                continue;
+            };
+
+            calibration.total += combine(first.value, last.value);
+            calibration.lines += 1;
+        }
+
+        Ok(calibration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [(&str, i64, i64); 7] = [
+        ("two1nine", 11, 29),
+        ("eightwothree", 0, 83),
+        ("abcone2threexyz", 22, 13),
+        ("xtwone3four", 33, 24),
+        ("4nineeightseven2", 42, 42),
+        ("zoneight234", 24, 14),
+        ("7pqrstsixteen", 77, 76),
+    ];
+
+    #[test]
+    fn test_value() {
+        let digits = CalibrationDecoder::new(Mode::DigitsOnly);
+        let words = CalibrationDecoder::new(Mode::DigitsAndWords);
+
+        for (line, digits_only, digits_and_words) in EXAMPLE {
+            assert_eq!(digits_only, digits.value(line), "{}", line);
+            assert_eq!(digits_and_words, words.value(line), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_total() {
+        let lines = EXAMPLE.map(|(line, _, _)| line);
+
+        assert_eq!(209, CalibrationDecoder::new(Mode::DigitsOnly).total(lines));
+        assert_eq!(
+            281,
+            CalibrationDecoder::new(Mode::DigitsAndWords).total(lines)
+        );
+    }
+
+    #[test]
+    fn test_other_languages() {
+        let cases = [
+            ("spanish", "xtresiete", 37),
+            ("spanish", "cincocho4", 54),
+            ("french", "troisix", 36),
+            ("french", "2septrois", 23),
+            ("german", "dreins", 31),
+            ("german", "sechsiebenfünf", 65),
+            ("german", "zweins", 21),
+        ];
+
+        for (language, line, value) in cases {
+            let decoder =
+                CalibrationDecoder::with_vocabulary(&Vocabulary::builtin(language).unwrap());
+            assert_eq!(value, decoder.value(line), "{} {}", language, line);
+        }
+    }
+
+    #[test]
+    fn test_custom_vocabulary() {
+        let mut vocabulary = Vocabulary::new();
+        vocabulary.insert("zero", 0);
+        vocabulary.insert("a", 1);
+        vocabulary.insert("ab", 2);
+
+        let decoder = CalibrationDecoder::with_vocabulary(&vocabulary);
+
+        // "ab" and "a" start at the same place, so the longer one wins.
+        assert_eq!(20, decoder.value("xabzero"));
+        assert_eq!(0, decoder.value("one"));
+    }
+
+    #[test]
+    fn test_no_digits_policy() {
+        let decoder = CalibrationDecoder::new(Mode::DigitsOnly);
+        let input = "a1b\nnothing\n\n2\nnone\n";
+
+        let zero = decoder.decode(input, NoDigits::Zero).unwrap();
+        assert_eq!(
+            (33, 4, vec![2, 5]),
+            (zero.total, zero.lines, zero.without_digits)
+        );
+
+        let skip = decoder.decode(input, NoDigits::Skip).unwrap();
+        assert_eq!(
+            (33, 2, vec![2, 5]),
+            (skip.total, skip.lines, skip.without_digits)
+        );
+
+        let error = decoder.decode(input, NoDigits::Error).unwrap_err();
+        assert_eq!((2, "nothing"), (error.line, error.snippet.as_str()));
+    }
+
+    #[test]
+    fn test_unicode_noise() {
+        let decoder = CalibrationDecoder::new(Mode::DigitsAndWords);
+
+        assert_eq!(37, decoder.value("🚀three🎄é7ñ"));
+        assert_eq!(55, decoder.value("café five crème brûlée"));
+        assert_eq!(0, decoder.value("😀😀😀"));
+        assert_eq!(12, decoder.value("1日本語二2"));
+    }
+
+    #[test]
+    fn test_full_width_digits() {
+        let line = "x３yfour５";
+
+        assert_eq!(
+            44,
+            CalibrationDecoder::new(Mode::DigitsAndWords).value(line)
+        );
+        assert_eq!(
+            35,
+            CalibrationDecoder::new(Mode::DigitsAndWords)
+                .with_full_width_digits()
+                .value(line)
+        );
+        assert_eq!(
+            13,
+            CalibrationDecoder::new(Mode::DigitsOnly)
+                .with_full_width_digits()
+                .value("１２３")
+        );
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        let decoder = CalibrationDecoder::new(Mode::DigitsAndWords);
+
+        assert_eq!(82, decoder.value("eightwo"));
+        assert_eq!(21, decoder.value("twone"));
+        assert_eq!(18, decoder.value("oneight"));
+    }
+
+    #[test]
+    fn test_compound() {
+        let decoder = CalibrationDecoder::new(Mode::Compound);
+
+        assert_eq!(174, decoder.value("xseventeen4"));
+        assert_eq!(2121, decoder.value("twenty-one"));
+        assert_eq!(9920, decoder.value("ninety-nine and twenty"));
+        assert_eq!(1140, decoder.value("eleven forty"));
+        assert_eq!(83, decoder.value("eightwothree"));
+
+        // Without the hyphen, the tens and the unit are two numbers.
+        assert_eq!(203, decoder.value("twentythree"));
+
+        // Outside of this mode, only the `one` counts.
+        assert_eq!(
+            11,
+            CalibrationDecoder::new(Mode::DigitsAndWords).value("twenty-one")
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt::Write;
+
+use aoc_common::{lines, ParseError};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{CalibrationDecoder, NoDigits};
+
+/// Annotates every line of `input` with the tokens the decoder found on it, which of them were
+/// used, and the calibration value they make. Blank lines are left out, and lines without digits
+/// are dealt with as `policy` says, just like [`CalibrationDecoder::decode`] does, so the values
+/// add up to the total it ends with.
+///
+/// The carets line up under the tokens on a terminal, wide characters like `１` included.
+///
+/// ```text
+/// 2 | eightwothree
+///   | ^^^^^         eight = 8 at column 1, first
+///   |     ^^^       two = 2 at column 5
+///   |        ^^^^^  three = 3 at column 8, last
+///   = 83
+/// ```
+pub fn explain(
+    decoder: &CalibrationDecoder,
+    input: &str,
+    policy: NoDigits,
+) -> Result<String, ParseError> {
+    let calibration = decoder.decode(input, policy)?;
+
+    let last_number = lines(input).last().map_or(0, |line| line.number);
+    let gutter = " ".repeat(last_number.to_string().len());
+
+    let mut output = String::new();
+    let mut zeroes = vec![];
+
+    for line in lines(input) {
+        let text = line.text;
+        let width = text.width();
+
+        writeln!(output, "{:>2$} | {}", line.number, text, gutter.len()).unwrap();
+
+        let chosen = decoder.first_and_last(text);
+
+        for token in decoder.tokens(text) {
+            let before = &text[..token.start];
+            let word = &text[token.start..token.end];
+
+            let role = match chosen {
+                Some((first, last)) if token == first && token == last => ", first and last",
+                Some((first, _)) if token == first => ", first",
+                Some((_, last)) if token == last => ", last",
+                _ => "",
+            };
+
+            writeln!(
+                output,
+                "{} | {}{:<width$}  {} = {} at column {}{}",
+                gutter,
+                " ".repeat(before.width()),
+                "^".repeat(word.width()),
+                word,
+                token.value,
+                before.chars().count() + 1,
+                role,
+                width = width - before.width()
+            )
+            .unwrap();
+        }
+
+        match chosen {
+            Some(_) => {
+                let value = decoder.value(text);
+                if value == 0 {
+                    zeroes.push(line.number);
+                }
+
+                writeln!(output, "{} = {}", gutter, value).unwrap();
+            }
+            None if policy == NoDigits::Skip => {
+                writeln!(output, "{} = left out, no digits", gutter).unwrap();
+            }
+            None => {
+                zeroes.push(line.number);
+                writeln!(output, "{} = 0, no digits", gutter).unwrap();
+            }
+        }
+    }
+
+    writeln!(output, "\nTotal: {}", calibration.total).unwrap();
+
+    let list = |numbers: &[usize]| -> String {
+        let numbers: Vec<String> = numbers.iter().map(usize::to_string).collect();
+        numbers.join(", ")
+    };
+
+    if zeroes.is_empty() {
+        writeln!(output, "Every line adds to the total").unwrap();
+    } else {
+        writeln!(output, "Lines that add nothing: {}", list(&zeroes)).unwrap();
+    }
+
+    if policy == NoDigits::Skip && !calibration.without_digits.is_empty() {
+        writeln!(
+            output,
+            "Lines left out: {}",
+            list(&calibration.without_digits)
+        )
+        .unwrap();
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mode;
+
+    #[test]
+    fn test_explain() {
+        let decoder = CalibrationDecoder::new(Mode::DigitsAndWords);
+
+        let expected = "\
+1 | eightwothree
+  | ^^^^^         eight = 8 at column 1, first
+  |     ^^^       two = 2 at column 5
+  |        ^^^^^  three = 3 at column 8, last
+  = 83
+2 | xyz
+  = 0, no digits
+4 | é7
+  |  ^  7 = 7 at column 2, first and last
+  = 77
+
+Total: 160
+Lines that add nothing: 2
+";
+
+        let input = "eightwothree\nxyz\n\né7\n";
+        assert_eq!(expected, explain(&decoder, input, NoDigits::Zero).unwrap());
+    }
+
+    #[test]
+    fn test_explain_policy() {
+        let decoder = CalibrationDecoder::new(Mode::DigitsOnly);
+        let input = "a1\nnothing\n";
+
+        let skipped = explain(&decoder, input, NoDigits::Skip).unwrap();
+        assert!(skipped.contains("  = left out, no digits\n"));
+        assert!(skipped.ends_with("Total: 11\nEvery line adds to the total\nLines left out: 2\n"));
+
+        let error = explain(&decoder, input, NoDigits::Error).unwrap_err();
+        assert_eq!(2, error.line);
+    }
+
+    #[test]
+    fn test_explain_wide_characters() {
+        let decoder = CalibrationDecoder::new(Mode::DigitsOnly).with_full_width_digits();
+
+        let expected = "\
+1 | 日１x2
+  |   ^^    １ = 1 at column 2, first
+  |      ^  2 = 2 at column 4, last
+  = 12
+";
+
+        let output = explain(&decoder, "日１x2", NoDigits::Zero).unwrap();
+        assert!(output.starts_with(expected), "{}", output);
+    }
+}
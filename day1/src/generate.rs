@@ -0,0 +1,53 @@
+use aoc_common::Rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Spelled digits that share letters, so the first and last digit have to be read carefully.
+const OVERLAPS: [&str; 8] = [
+    "oneight",
+    "twone",
+    "threeight",
+    "fiveight",
+    "sevenine",
+    "eightwo",
+    "eighthree",
+    "nineight",
+];
+
+/// Generates `size` calibration lines mixing digits, spelled digits and other letters. Every line
+/// has at least one digit.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for _ in 0..size {
+        let mut line = String::new();
+        let mut has_digit = false;
+
+        for _ in 0..rng.range(1, 8) {
+            match rng.range(0, 3) {
+                0 => {
+                    line.push(char::from(b'0' + rng.range(1, 9) as u8));
+                    has_digit = true;
+                }
+                1 => line.push_str(rng.pick::<&str>(&WORDS)),
+                2 => line.push_str(rng.pick::<&str>(&OVERLAPS)),
+                _ => {
+                    for _ in 0..rng.range(1, 4) {
+                        line.push(char::from(b'a' + rng.range(0, 25) as u8));
+                    }
+                }
+            }
+        }
+
+        if !has_digit {
+            line.push(char::from(b'0' + rng.range(1, 9) as u8));
+        }
+
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    output
+}
@@ -0,0 +1,33 @@
+pub mod decoder;
+pub mod explain;
+pub mod generate;
+pub mod matcher;
+pub mod stream;
+pub mod vocabulary;
+
+use aoc_common::{Error, Solution};
+
+pub use decoder::{Calibration, CalibrationDecoder, Mode, NoDigits};
+pub use stream::StreamDecoder;
+pub use vocabulary::Vocabulary;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(lines: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(CalibrationDecoder::new(Mode::DigitsOnly).total(lines.iter().map(String::as_str)))
+    }
+
+    fn part2(lines: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(CalibrationDecoder::new(Mode::DigitsAndWords).total(lines.iter().map(String::as_str)))
+    }
+}
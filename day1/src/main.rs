@@ -1,64 +1,212 @@
-use std::env;
-
-use std::fs::read_to_string;
-
-// New method!
-// For each line, find all text numbers and digit numbers. Add each found to a new string. Then just
-// use the first and last characters from that string.
-
-fn main() {
-    let path = env::args().nth(1).unwrap();
-
-    let mut calibration_values: Vec<i64> = Vec::new();
-
-    for line in read_to_string(path).unwrap().lines().map(String::from) {
-        // For the whole line... get each substring from [i..] where i starts at 0.
-        // Check if that substring starts with one of the spelled numbers OR is a number.
-        // Add to the new string.
-
-        let mut num_line = String::new();
-
-        for index in 0..line.len() {
-            let substring_to_end = &line[index..];
-
-            let first_char = substring_to_end.chars().nth(0).unwrap();
-
-            if first_char.is_digit(10) {
-                num_line.push(first_char);
-            } else if substring_to_end.starts_with("one") {
-                num_line.push('1');
-            } else if substring_to_end.starts_with("two") {
-                num_line.push('2');
-            } else if substring_to_end.starts_with("three") {
-                num_line.push('3');
-            } else if substring_to_end.starts_with("four") {
-                num_line.push('4');
-            } else if substring_to_end.starts_with("five") {
-                num_line.push('5');
-            } else if substring_to_end.starts_with("six") {
-                num_line.push('6');
-            } else if substring_to_end.starts_with("seven") {
-                num_line.push('7');
-            } else if substring_to_end.starts_with("eight") {
-                num_line.push('8');
-            } else if substring_to_end.starts_with("nine") {
-                num_line.push('9');
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc_common::Error;
+use day1::{Calibration, CalibrationDecoder, Mode, NoDigits, StreamDecoder};
+
+const USAGE: &str = "\
+usage: day1-trebuchet <INPUT> [--part <1|2>] [--no-digits <zero|skip|error>] [--explain]
+                      [--compound] [--threads <N>]
+
+The input is read from stdin when it is -. It is read a chunk at a time, so it can be
+bigger than memory.
+
+--no-digits says what to do with lines that have no digits: count them as zero (the
+default), leave them out, or stop with an error.
+--explain shows the digits found on every line and the calibration value they make, with
+the words counting as digits unless --part 1 is given.
+--compound reads number words up to ninety-nine, like seventeen or twenty-one, as whole
+numbers in part 2.
+--threads decodes that many chunks of the input at once.";
+
+/// How many line numbers to list when reporting lines without digits.
+const LISTED_LINES: usize = 10;
+
+struct Args {
+    input: PathBuf,
+    part: Option<Mode>,
+    no_digits: NoDigits,
+    explain: bool,
+    compound: bool,
+    threads: usize,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut part: Option<Mode> = None;
+    let mut no_digits = NoDigits::default();
+    let mut explain = false;
+    let mut compound = false;
+    let mut threads = 1;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--part" => {
+                part = match value()?.as_str() {
+                    "1" => Some(Mode::DigitsOnly),
+                    "2" => Some(Mode::DigitsAndWords),
+                    other => return Err(format!("there is no part '{}'", other)),
+                };
+            }
+            "--no-digits" => {
+                no_digits = match value()?.as_str() {
+                    "zero" => NoDigits::Zero,
+                    "skip" => NoDigits::Skip,
+                    "error" => NoDigits::Error,
+                    other => return Err(format!("there is no policy '{}'", other)),
+                };
             }
+            "--explain" => explain = true,
+            "--compound" => compound = true,
+            "--threads" => {
+                let value = value()?;
+                threads = match value.parse() {
+                    Ok(threads) if threads > 0 => threads,
+                    _ => return Err(format!("'{}' is not a number of threads", value)),
+                };
+            }
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown argument '{}'", arg))
+            }
+            _ if input.is_none() => input = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
         }
+    }
+
+    Ok(Args {
+        input: input.ok_or("no input file given")?,
+        part,
+        no_digits,
+        explain,
+        compound,
+        threads,
+    })
+}
+
+fn print_calibration(number: u8, calibration: &Calibration, policy: NoDigits) {
+    println!("Part {}: {}", number, calibration.total);
+
+    let lines = &calibration.without_digits;
+    if lines.is_empty() {
+        return;
+    }
+
+    let mut listed: Vec<String> = lines
+        .iter()
+        .take(LISTED_LINES)
+        .map(usize::to_string)
+        .collect();
+    if lines.len() > LISTED_LINES {
+        listed.push("...".to_string());
+    }
+
+    let outcome = match policy {
+        NoDigits::Skip => "left out",
+        _ => "counted as 0",
+    };
+
+    let count = match lines.len() {
+        1 => "1 line".to_string(),
+        count => format!("{} lines", count),
+    };
+
+    println!(
+        "  {} without digits {}: {}",
+        count,
+        outcome,
+        listed.join(", ")
+    );
+}
 
-        let first = num_line.clone().chars().nth(0).unwrap();
-        let last = num_line.clone().chars().nth_back(0).unwrap();
+/// Opens the input file, or stdin for `-`.
+fn open(path: &Path) -> Result<Box<dyn Read>, Error> {
+    if path == Path::new("-") {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+
+    match File::open(path) {
+        Ok(file) => Ok(Box::new(file)),
+        Err(source) => Err(Error::Io {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
 
-        let mut calibration_value = String::new();
-        calibration_value.push(first);
-        calibration_value.push(last);
+fn run(args: Args) -> Result<(), Error> {
+    let mut reader = open(&args.input)?;
+
+    // --compound swaps the words of part 2 for the compound ones.
+    let words = match args.compound {
+        true => Mode::Compound,
+        false => Mode::DigitsAndWords,
+    };
+    let part = args.part.map(|mode| match mode {
+        Mode::DigitsOnly => Mode::DigitsOnly,
+        _ => words,
+    });
+
+    if args.explain {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(|source| Error::Io {
+                path: args.input.clone(),
+                source,
+            })?;
+
+        let decoder = CalibrationDecoder::new(part.unwrap_or(words));
+        let explanation = day1::explain::explain(&decoder, &input, args.no_digits)
+            .map_err(|error| Error::from(error).with_file(&args.input))?;
+        print!("{}", explanation);
+
+        return Ok(());
+    }
 
-        calibration_values.push(calibration_value.parse::<i64>().unwrap_or_default());
+    let parts = match part {
+        Some(mode) => vec![mode],
+        None => vec![Mode::DigitsOnly, words],
+    };
+
+    // Every part is decoded in the same pass, so the input is only read once.
+    let decoders: Vec<CalibrationDecoder> =
+        parts.iter().copied().map(CalibrationDecoder::new).collect();
+    let calibrations = StreamDecoder::new(args.no_digits)
+        .with_threads(args.threads)
+        .decode_all(&decoders, reader, &args.input)?;
+
+    for (mode, calibration) in parts.into_iter().zip(calibrations) {
+        let number = match mode {
+            Mode::DigitsOnly => 1,
+            Mode::DigitsAndWords | Mode::Compound => 2,
+        };
+
+        print_calibration(number, &calibration, args.no_digits);
     }
 
-    let sum: i64 = calibration_values.iter().sum();
+    Ok(())
+}
 
-    println!("Sum: {}", sum);
+fn main() -> ExitCode {
+    aoc_common::init_logging(false);
 
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
 
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error.render());
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,333 @@
+/// A pattern found on a line. `start` and `end` are byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub start: usize,
+    pub end: usize,
+    pub value: u8,
+}
+
+impl Token {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether this token comes before `other` when reading from the left. When both start at
+    /// the same byte, the longer one wins.
+    fn starts_before(&self, other: &Token) -> bool {
+        (self.start, other.len()) < (other.start, self.len())
+    }
+}
+
+const NO_STATE: u32 = u32::MAX;
+
+/// Finds every occurrence of a set of patterns in one pass over a line, overlapping ones
+/// included, like the `two` and `one` in `twone`.
+///
+/// This is an Aho-Corasick automaton: a trie of the patterns where every missing edge has been
+/// filled in with where the longest matching suffix would continue, so each byte of the line is
+/// looked at exactly once.
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    /// One row of transitions per state, indexed by the next byte. State 0 is the root.
+    transitions: Vec<[u32; 256]>,
+    /// The patterns that end in each state, as their length and value, longest first.
+    outputs: Vec<Vec<(usize, u8)>>,
+    /// The length of the longest pattern.
+    longest: usize,
+}
+
+impl Matcher {
+    /// Builds the automaton for `(pattern, value)` pairs. Empty patterns are ignored.
+    pub fn new<'a>(patterns: impl IntoIterator<Item = (&'a str, u8)>) -> Self {
+        Matcher::from_bytes(
+            patterns
+                .into_iter()
+                .map(|(pattern, value)| (pattern.as_bytes().to_vec(), value)),
+        )
+    }
+
+    /// Patterns are matched byte by byte, so they don't have to be valid UTF-8 on their own.
+    fn from_bytes(patterns: impl IntoIterator<Item = (Vec<u8>, u8)>) -> Self {
+        let mut matcher = Matcher {
+            transitions: vec![[NO_STATE; 256]],
+            outputs: vec![vec![]],
+            longest: 0,
+        };
+
+        for (pattern, value) in patterns {
+            if !pattern.is_empty() {
+                matcher.insert(&pattern, value);
+            }
+        }
+
+        matcher.link();
+        matcher
+    }
+
+    fn insert(&mut self, pattern: &[u8], value: u8) {
+        let mut state = 0;
+
+        for &byte in pattern {
+            if self.transitions[state][byte as usize] == NO_STATE {
+                self.transitions[state][byte as usize] = self.transitions.len() as u32;
+                self.transitions.push([NO_STATE; 256]);
+                self.outputs.push(vec![]);
+            }
+
+            state = self.transitions[state][byte as usize] as usize;
+        }
+
+        self.outputs[state].push((pattern.len(), value));
+        self.longest = self.longest.max(pattern.len());
+    }
+
+    /// Fills in the missing edges, breadth first so that every state's suffix is complete before
+    /// the state itself.
+    fn link(&mut self) {
+        let mut suffix = vec![0; self.transitions.len()];
+        let mut queue = std::collections::VecDeque::new();
+
+        for byte in 0..256 {
+            match self.transitions[0][byte] {
+                NO_STATE => self.transitions[0][byte] = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..256 {
+                let fallback = self.transitions[suffix[state]][byte];
+
+                match self.transitions[state][byte] {
+                    NO_STATE => self.transitions[state][byte] = fallback,
+                    child => {
+                        let child = child as usize;
+                        suffix[child] = fallback as usize;
+
+                        // Anything that ends at the suffix also ends here.
+                        let inherited = self.outputs[suffix[child]].clone();
+                        self.outputs[child].extend(inherited);
+
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The pattern that starts first on the line.
+    ///
+    /// Patterns are found in the order they end, so a pattern found later could still start
+    /// earlier, but only until the scan is a whole pattern length past the best one so far. That
+    /// is where it stops, so the cost depends on how far in the first pattern is rather than on
+    /// the length of the line.
+    pub fn find_first(&self, line: &str) -> Option<Token> {
+        self.scan_first(line.bytes())
+    }
+
+    /// [`Matcher::find_first`] for any sequence of bytes, with offsets counted from its start.
+    fn scan_first(&self, bytes: impl Iterator<Item = u8>) -> Option<Token> {
+        let mut first: Option<Token> = None;
+        let mut state = 0;
+
+        for (index, byte) in bytes.enumerate() {
+            if first.is_some_and(|first| index >= first.start + self.longest) {
+                break;
+            }
+
+            state = self.transitions[state][byte as usize] as usize;
+
+            for &(len, value) in &self.outputs[state] {
+                let token = Token {
+                    start: index + 1 - len,
+                    end: index + 1,
+                    value,
+                };
+
+                if first.is_none_or(|first| token.starts_before(&first)) {
+                    first = Some(token);
+                }
+            }
+        }
+
+        first
+    }
+
+    /// Every pattern on the line, in the order they end.
+    pub fn find_iter<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Token> + 'a {
+        let mut state = 0;
+
+        line.bytes().enumerate().flat_map(move |(index, byte)| {
+            state = self.transitions[state][byte as usize] as usize;
+
+            self.outputs[state].iter().map(move |&(len, value)| Token {
+                start: index + 1 - len,
+                end: index + 1,
+                value,
+            })
+        })
+    }
+}
+
+/// Finds the pattern that ends last on a line by reading it from the end.
+///
+/// This is a [`Matcher`] for the reversed patterns run over the reversed line, so the pattern
+/// that starts first on the reversed line is the one that ends last on the line.
+#[derive(Debug, Clone)]
+pub struct ReverseMatcher {
+    reversed: Matcher,
+}
+
+impl ReverseMatcher {
+    pub fn new<'a>(patterns: impl IntoIterator<Item = (&'a str, u8)>) -> Self {
+        // The line is read backwards a byte at a time, so the patterns are reversed byte by
+        // byte too, even where that splits up a multi-byte character.
+        let reversed = patterns.into_iter().map(|(pattern, value)| {
+            let mut bytes = pattern.as_bytes().to_vec();
+            bytes.reverse();
+            (bytes, value)
+        });
+
+        ReverseMatcher {
+            reversed: Matcher::from_bytes(reversed),
+        }
+    }
+
+    /// The pattern that ends last on the line. When two end at the same byte, the longer one
+    /// wins, so a pattern inside a longer one, like `one` in `twenty-one`, is never the last.
+    pub fn find_last(&self, line: &str) -> Option<Token> {
+        let reversed = self.reversed.scan_first(line.bytes().rev())?;
+
+        Some(Token {
+            start: line.len() - reversed.end,
+            end: line.len() - reversed.start,
+            value: reversed.value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(matcher: &Matcher, line: &str) -> Vec<u8> {
+        matcher.find_iter(line).map(|token| token.value).collect()
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let matcher = Matcher::new([("one", 1), ("two", 2), ("eight", 8)]);
+
+        assert_eq!(vec![2, 1], values(&matcher, "twone"));
+        assert_eq!(vec![8, 2], values(&matcher, "eightwo"));
+        assert_eq!(vec![1, 8], values(&matcher, "oneight"));
+    }
+
+    #[test]
+    fn test_suffix_patterns() {
+        let matcher = Matcher::new([("he", 1), ("she", 2), ("hers", 3)]);
+
+        let tokens: Vec<Token> = matcher.find_iter("ushers").collect();
+
+        assert_eq!(
+            vec![
+                Token {
+                    start: 1,
+                    end: 4,
+                    value: 2
+                },
+                Token {
+                    start: 2,
+                    end: 4,
+                    value: 1
+                },
+                Token {
+                    start: 2,
+                    end: 6,
+                    value: 3
+                },
+            ],
+            tokens
+        );
+    }
+
+    #[test]
+    fn test_find_first_and_last() {
+        let patterns = [("one", 1), ("two", 2), ("eight", 8), ("7", 7)];
+        let matcher = Matcher::new(patterns);
+        let reverse = ReverseMatcher::new(patterns);
+
+        let line = "xeightwo7twone";
+
+        assert_eq!(Some(8), matcher.find_first(line).map(|token| token.value));
+        assert_eq!(Some(1), reverse.find_last(line).map(|token| token.value));
+        assert_eq!(None, matcher.find_first("xyz"));
+        assert_eq!(None, reverse.find_last("xyz"));
+    }
+
+    #[test]
+    fn test_pattern_inside_another() {
+        // "b" ends first, but "abc" starts first.
+        let patterns = [("abc", 1), ("b", 2), ("bc", 3)];
+        let matcher = Matcher::new(patterns);
+        let reverse = ReverseMatcher::new(patterns);
+
+        assert_eq!(
+            Some(1),
+            matcher.find_first("xabcx").map(|token| token.value)
+        );
+        assert_eq!(Some(1), reverse.find_last("xabcx").map(|token| token.value));
+        assert_eq!(Some(2), reverse.find_last("xabx").map(|token| token.value));
+    }
+
+    #[test]
+    fn test_matches_full_scan() {
+        let patterns = [
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("eight", 8),
+            ("nine", 9),
+        ];
+        let matcher = Matcher::new(patterns);
+        let reverse = ReverseMatcher::new(patterns);
+
+        let input = crate::generate::generate(&mut aoc_common::Rng::new(3), 500);
+
+        for line in input.lines() {
+            let tokens: Vec<Token> = matcher.find_iter(line).collect();
+
+            let first = tokens
+                .iter()
+                .copied()
+                .reduce(|a, b| if b.starts_before(&a) { b } else { a });
+            // The one that ends last, and the longest of those.
+            let last = tokens
+                .iter()
+                .copied()
+                .max_by_key(|token| (token.end, token.len()));
+
+            assert_eq!(first, matcher.find_first(line), "{}", line);
+            assert_eq!(last, reverse.find_last(line), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_multibyte_text() {
+        let matcher = Matcher::new([("one", 1)]);
+
+        assert_eq!(vec![1], values(&matcher, "é€one"));
+    }
+
+    #[test]
+    fn test_multibyte_patterns() {
+        let patterns = [("fünf", 5), ("vier", 4)];
+        let reverse = ReverseMatcher::new(patterns);
+
+        assert_eq!(vec![5, 4], values(&Matcher::new(patterns), "fünfvier"));
+        assert_eq!(
+            Some(5),
+            reverse.find_last("vierfünf").map(|token| token.value)
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use aoc_common::{Error, ParseError};
+
+use crate::{Calibration, CalibrationDecoder, NoDigits};
+
+/// How many bytes are read at a time, unless another size is given.
+pub const CHUNK_SIZE: usize = 1 << 20;
+
+/// Decodes a calibration document straight from a file or stdin, a chunk of whole lines at a
+/// time, so only a few chunks are ever in memory however big the document is. The chunks can be
+/// decoded on several threads at once.
+///
+/// The result is always the same as [`CalibrationDecoder::decode`] on the whole document, line
+/// numbers and errors included.
+#[derive(Debug, Clone)]
+pub struct StreamDecoder {
+    policy: NoDigits,
+    threads: usize,
+    chunk_size: usize,
+}
+
+impl StreamDecoder {
+    pub fn new(policy: NoDigits) -> Self {
+        StreamDecoder {
+            policy,
+            threads: 1,
+            chunk_size: CHUNK_SIZE,
+        }
+    }
+
+    /// Decodes up to `threads` chunks at once, each on its own thread.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Reads about `bytes` at a time. Chunks always end at the end of a line, so a line longer
+    /// than this makes its chunk longer.
+    pub fn with_chunk_size(mut self, bytes: usize) -> Self {
+        self.chunk_size = bytes.max(1);
+        self
+    }
+
+    /// Decodes everything `reader` gives. `path` is where it comes from, for errors.
+    pub fn decode(
+        &self,
+        decoder: &CalibrationDecoder,
+        reader: impl Read,
+        path: &Path,
+    ) -> Result<Calibration, Error> {
+        let mut calibrations = self.decode_all(std::slice::from_ref(decoder), reader, path)?;
+        Ok(calibrations.remove(0))
+    }
+
+    /// Decodes everything `reader` gives with each of `decoders`, reading it only once. The
+    /// calibrations are in the same order as the decoders.
+    pub fn decode_all(
+        &self,
+        decoders: &[CalibrationDecoder],
+        reader: impl Read,
+        path: &Path,
+    ) -> Result<Vec<Calibration>, Error> {
+        let mut calibrations = vec![Calibration::default(); decoders.len()];
+        let mut chunks = Chunks::new(reader, self.chunk_size, path);
+
+        loop {
+            // A chunk that can't be read is only reported once the chunks before it have been
+            // decoded, as they might hold an earlier error.
+            let mut batch = vec![];
+            let mut read_error = None;
+
+            for chunk in (&mut chunks).take(self.threads) {
+                match chunk {
+                    Ok(chunk) => batch.push(chunk),
+                    Err(error) => {
+                        read_error = Some(error);
+                        break;
+                    }
+                }
+            }
+
+            let results = match batch.as_slice() {
+                [] => vec![],
+                [chunk] => vec![self.decode_chunk(decoders, chunk)],
+                _ => thread::scope(|scope| {
+                    let threads: Vec<_> = batch
+                        .iter()
+                        .map(|chunk| scope.spawn(|| self.decode_chunk(decoders, chunk)))
+                        .collect();
+
+                    threads
+                        .into_iter()
+                        .map(|thread| thread.join().unwrap())
+                        .collect()
+                }),
+            };
+
+            // The results are merged in the order of the chunks, so the first error in the
+            // document is the one reported.
+            for result in results {
+                let parts = result.map_err(|error| error.with_file(path))?;
+
+                for (calibration, part) in calibrations.iter_mut().zip(parts) {
+                    calibration.total += part.total;
+                    calibration.lines += part.lines;
+                    calibration.without_digits.extend(part.without_digits);
+                }
+            }
+
+            if let Some(error) = read_error {
+                return Err(error);
+            }
+
+            if batch.is_empty() {
+                break;
+            }
+        }
+
+        Ok(calibrations)
+    }
+
+    fn decode_chunk(
+        &self,
+        decoders: &[CalibrationDecoder],
+        chunk: &Chunk,
+    ) -> Result<Vec<Calibration>, ParseError> {
+        decoders
+            .iter()
+            .map(|decoder| {
+                let mut calibration =
+                    decoder
+                        .decode(&chunk.text, self.policy)
+                        .map_err(|mut error| {
+                            error.line += chunk.lines_before;
+                            error
+                        })?;
+
+                for line in &mut calibration.without_digits {
+                    *line += chunk.lines_before;
+                }
+
+                Ok(calibration)
+            })
+            .collect()
+    }
+}
+
+/// Some whole lines of the document.
+struct Chunk {
+    text: String,
+    /// How many lines of the document come before this chunk.
+    lines_before: usize,
+}
+
+/// Cuts what a reader gives into chunks of whole lines.
+struct Chunks<R> {
+    reader: R,
+    chunk_size: usize,
+    path: PathBuf,
+    /// What was read after the end of the last line of the previous chunk.
+    rest: Vec<u8>,
+    lines_read: usize,
+    finished: bool,
+}
+
+impl<R: Read> Chunks<R> {
+    fn new(reader: R, chunk_size: usize, path: &Path) -> Self {
+        Chunks {
+            reader,
+            chunk_size,
+            path: path.to_path_buf(),
+            rest: vec![],
+            lines_read: 0,
+            finished: false,
+        }
+    }
+
+    fn read_chunk(&mut self) -> Result<Option<Chunk>, Error> {
+        let mut bytes = std::mem::take(&mut self.rest);
+
+        let end = loop {
+            let searched = bytes.len();
+            let read = (&mut self.reader)
+                .take(self.chunk_size as u64)
+                .read_to_end(&mut bytes)
+                .map_err(|source| Error::Io {
+                    path: self.path.clone(),
+                    source,
+                })?;
+
+            if read < self.chunk_size {
+                self.finished = true;
+                break bytes.len();
+            }
+
+            if let Some(index) = bytes[searched..].iter().rposition(|&byte| byte == b'\n') {
+                break searched + index + 1;
+            }
+        };
+
+        self.rest = bytes.split_off(end);
+
+        if bytes.is_empty() {
+            return Ok(None);
+        }
+
+        let lines_before = self.lines_read;
+        self.lines_read += bytes.iter().filter(|&&byte| byte == b'\n').count();
+
+        let text = String::from_utf8(bytes).map_err(|error| {
+            let offset = error.utf8_error().valid_up_to();
+            let text = String::from_utf8_lossy(error.as_bytes());
+
+            let mut error = ParseError::at_offset(
+                &text,
+                offset,
+                char::REPLACEMENT_CHARACTER.len_utf8(),
+                "not valid UTF-8",
+            );
+            error.line += lines_before;
+            error
+        })?;
+
+        Ok(Some(Chunk { text, lines_before }))
+    }
+}
+
+impl<R: Read> Iterator for Chunks<R> {
+    type Item = Result<Chunk, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        self.read_chunk().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Rng;
+
+    use super::*;
+    use crate::Mode;
+
+    fn decoders() -> Vec<CalibrationDecoder> {
+        vec![
+            CalibrationDecoder::new(Mode::DigitsOnly),
+            CalibrationDecoder::new(Mode::DigitsAndWords),
+        ]
+    }
+
+    #[test]
+    fn test_same_as_decode() {
+        let mut input = crate::generate::generate(&mut Rng::new(3), 2000);
+        input.push_str("\nno digits here\n\nlast line without a newline 7");
+
+        for policy in [NoDigits::Zero, NoDigits::Skip] {
+            let expected: Vec<Calibration> = decoders()
+                .iter()
+                .map(|decoder| decoder.decode(&input, policy).unwrap())
+                .collect();
+
+            for (chunk_size, threads) in [(1, 1), (7, 1), (100, 4), (CHUNK_SIZE, 3)] {
+                let calibrations = StreamDecoder::new(policy)
+                    .with_chunk_size(chunk_size)
+                    .with_threads(threads)
+                    .decode_all(&decoders(), input.as_bytes(), Path::new("input"))
+                    .unwrap();
+
+                assert_eq!(expected, calibrations, "{} {}", chunk_size, threads);
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_line() {
+        let input = "1\n2\nthree\nnothing\n5\nnone\n";
+        let decoder = CalibrationDecoder::new(Mode::DigitsAndWords);
+
+        let error = StreamDecoder::new(NoDigits::Error)
+            .with_chunk_size(4)
+            .with_threads(3)
+            .decode(&decoder, input.as_bytes(), Path::new("input"))
+            .unwrap_err();
+
+        let Error::Parse(error) = error else {
+            panic!("expected a parse error, got {:?}", error);
+        };
+        assert_eq!((4, "nothing"), (error.line, error.snippet.as_str()));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let input = b"1\n2\nab\xffc\n";
+        let decoder = CalibrationDecoder::new(Mode::DigitsOnly);
+
+        let error = StreamDecoder::new(NoDigits::Zero)
+            .with_chunk_size(2)
+            .decode(&decoder, &input[..], Path::new("input"))
+            .unwrap_err();
+
+        let Error::Parse(error) = error else {
+            panic!("expected a parse error, got {:?}", error);
+        };
+        assert_eq!((3, 3), (error.line, error.column));
+    }
+
+    #[test]
+    fn test_error_before_invalid_utf8() {
+        let input = b"1\nnothing\n2\nab\xffc\n";
+        let decoder = CalibrationDecoder::new(Mode::DigitsOnly);
+
+        for threads in [1, 4] {
+            let error = StreamDecoder::new(NoDigits::Error)
+                .with_chunk_size(2)
+                .with_threads(threads)
+                .decode(&decoder, &input[..], Path::new("input"))
+                .unwrap_err();
+
+            let Error::Parse(error) = error else {
+                panic!("expected a parse error, got {:?}", error);
+            };
+            assert_eq!(
+                (2, "nothing"),
+                (error.line, error.snippet.as_str()),
+                "{}",
+                threads
+            );
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use aoc_common::{lines, Error, ParseError};
+
+/// The words that are read as digits, and the digit each one stands for.
+///
+/// A vocabulary file has one `word = digit` pair per line. Blank lines and lines starting with
+/// `#` are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Vocabulary {
+    words: Vec<(String, u8)>,
+}
+
+/// The vocabularies that come with the decoder, by name.
+pub const BUILTIN: [&str; 4] = ["english", "spanish", "french", "german"];
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Vocabulary { words: vec![] }
+    }
+
+    /// Adds a word, or changes the digit of a word that is already there.
+    ///
+    /// # Panics
+    ///
+    /// If the word is empty or the digit is above 9.
+    pub fn insert(&mut self, word: impl Into<String>, digit: u8) {
+        let word = word.into();
+
+        assert!(!word.is_empty(), "number words can't be empty");
+        assert!(digit <= 9, "{} is not a digit", digit);
+
+        match self.words.iter_mut().find(|(known, _)| *known == word) {
+            Some(entry) => entry.1 = digit,
+            None => self.words.push((word, digit)),
+        }
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.words
+            .iter()
+            .map(|(word, digit)| (word.as_str(), *digit))
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Reads a vocabulary file.
+    pub fn parse(contents: &str) -> Result<Vocabulary, ParseError> {
+        let mut vocabulary = Vocabulary::new();
+
+        for line in lines(contents) {
+            let text = line.text.trim();
+
+            if text.starts_with('#') {
+                continue;
+            }
+
+            let Some((word, digit)) = text.split_once('=') else {
+                return Err(line.error(line.text, "expected 'word = digit'"));
+            };
+
+            let word = word.trim();
+            if word.is_empty() {
+                return Err(line.error(line.text, "expected a word before '='"));
+            }
+
+            let digit = digit.trim();
+            match line.parse::<u8>(digit, "a digit from 0 to 9")? {
+                digit @ 0..=9 => vocabulary.insert(word, digit),
+                _ => return Err(line.error(digit, "expected a digit from 0 to 9")),
+            }
+        }
+
+        Ok(vocabulary)
+    }
+
+    /// Reads a vocabulary file from disk.
+    pub fn load(path: impl AsRef<Path>) -> Result<Vocabulary, Error> {
+        let contents = aoc_common::read_input(&path)?;
+
+        Vocabulary::parse(&contents).map_err(|error| Error::from(error.with_file(path)))
+    }
+
+    /// One of the [`BUILTIN`] vocabularies.
+    pub fn builtin(name: &str) -> Option<Vocabulary> {
+        let contents = match name {
+            "english" => include_str!("../vocabularies/english.txt"),
+            "spanish" => include_str!("../vocabularies/spanish.txt"),
+            "french" => include_str!("../vocabularies/french.txt"),
+            "german" => include_str!("../vocabularies/german.txt"),
+            _ => return None,
+        };
+
+        Some(Vocabulary::parse(contents).expect("the built-in vocabularies are valid"))
+    }
+
+    /// The words the puzzle uses.
+    pub fn english() -> Vocabulary {
+        Vocabulary::builtin("english").unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin() {
+        for name in BUILTIN {
+            let vocabulary = Vocabulary::builtin(name).unwrap();
+
+            let mut digits: Vec<u8> = vocabulary.words().map(|(_, digit)| digit).collect();
+            digits.dedup();
+
+            assert_eq!((1..=9).collect::<Vec<u8>>(), digits, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let vocabulary = Vocabulary::parse("# Dutch\n\neen = 1\n  twee=2 \n").unwrap();
+
+        assert_eq!(
+            vec![("een", 1), ("twee", 2)],
+            vocabulary.words().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Vocabulary::parse("een = 1\ntwee 2\n").unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+
+        let error = Vocabulary::parse("tien = 10\n").unwrap_err();
+        assert_eq!(
+            (1, 8, "10"),
+            (error.line, error.column, error.snippet.as_str())
+        );
+    }
+
+    #[test]
+    fn test_insert_replaces() {
+        let mut vocabulary = Vocabulary::new();
+        vocabulary.insert("one", 1);
+        vocabulary.insert("one", 7);
+
+        assert_eq!(vec![("one", 7)], vocabulary.words().collect::<Vec<_>>());
+    }
+}
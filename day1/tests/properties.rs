@@ -0,0 +1,127 @@
+//! Property tests for the calibration decoder, checking it against a brute force reading of the
+//! line and against lines built from known pieces.
+
+use std::path::Path;
+
+use day1::matcher::{Matcher, ReverseMatcher, Token};
+use day1::{CalibrationDecoder, Mode, NoDigits, StreamDecoder};
+use proptest::prelude::*;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Letters that aren't in any of the words, so they can never be part of one.
+const NOISE: &str = "[abcdjklmpqyzABCDJKLMPQYZ .,🎄é]*";
+
+/// Lines that are mostly made of the letters of the words, so words and near misses turn up
+/// often, with some digits and multi-byte characters thrown in.
+const LINE: &str = "[onetwhrfuivsxg1-9é日 ]{0,40}";
+
+/// What the value of the line is, found by trying every word at every byte.
+fn brute_force_value(line: &str, words: bool) -> i64 {
+    let mut tokens = vec![];
+
+    for start in 0..line.len() {
+        let rest = &line.as_bytes()[start..];
+
+        for (digit, word) in (1..).zip(WORDS) {
+            let mut patterns = vec![digit.to_string()];
+            if words {
+                patterns.push(word.to_string());
+            }
+
+            for pattern in patterns {
+                if rest.starts_with(pattern.as_bytes()) {
+                    tokens.push((start, start + pattern.len(), digit));
+                }
+            }
+        }
+    }
+
+    let first = tokens.iter().min_by_key(|(start, _, _)| *start);
+    let last = tokens.iter().max_by_key(|(_, end, _)| *end);
+
+    match (first, last) {
+        (Some(first), Some(last)) => first.2 * 10 + last.2,
+        _ => 0,
+    }
+}
+
+/// A line made of digits and words, which can't run into each other.
+fn pieces() -> impl Strategy<Value = Vec<(String, i64)>> {
+    let piece = prop_oneof![
+        (1..=9i64).prop_map(|digit| (digit.to_string(), digit)),
+        (1..=9i64).prop_map(|digit| (WORDS[digit as usize - 1].to_string(), digit)),
+    ];
+
+    prop::collection::vec(piece, 1..8)
+}
+
+proptest! {
+    #[test]
+    fn noise_never_changes_the_value(
+        pieces in pieces(),
+        noise in prop::collection::vec(NOISE, 9),
+    ) {
+        let decoder = CalibrationDecoder::new(Mode::DigitsAndWords);
+
+        let mut line = String::new();
+        for (index, (piece, _)) in pieces.iter().enumerate() {
+            line.push_str(&noise[index % noise.len()]);
+            line.push_str(piece);
+        }
+        line.push_str(&noise[pieces.len() % noise.len()]);
+
+        let expected = pieces[0].1 * 10 + pieces[pieces.len() - 1].1;
+        prop_assert_eq!(expected, decoder.value(&line), "{}", line);
+    }
+
+    #[test]
+    fn value_matches_brute_force(line in LINE) {
+        let digits = CalibrationDecoder::new(Mode::DigitsOnly);
+        let words = CalibrationDecoder::new(Mode::DigitsAndWords);
+
+        prop_assert_eq!(brute_force_value(&line, false), digits.value(&line));
+        prop_assert_eq!(brute_force_value(&line, true), words.value(&line));
+    }
+
+    #[test]
+    fn reverse_matches_forward_scan(
+        line in "[a-eé]{0,30}",
+        patterns in prop::collection::vec("[a-eé]{1,4}", 1..6),
+    ) {
+        let patterns: Vec<(&str, u8)> = patterns.iter().map(String::as_str).zip(0..).collect();
+        let matcher = Matcher::new(patterns.iter().copied());
+        let reverse = ReverseMatcher::new(patterns.iter().copied());
+
+        // Of the patterns that end last, the longest.
+        let expected: Option<Token> = matcher
+            .find_iter(&line)
+            .max_by_key(|token| (token.end, token.end - token.start));
+
+        prop_assert_eq!(
+            expected.map(|token| (token.start, token.end)),
+            reverse.find_last(&line).map(|token| (token.start, token.end))
+        );
+    }
+
+    #[test]
+    fn stream_matches_decode(
+        lines in prop::collection::vec(LINE, 0..50),
+        chunk_size in 1..64usize,
+        threads in 1..4usize,
+    ) {
+        let input = lines.join("\n");
+        let decoder = CalibrationDecoder::new(Mode::DigitsAndWords);
+
+        let expected = decoder.decode(&input, NoDigits::Skip).unwrap();
+        let streamed = StreamDecoder::new(NoDigits::Skip)
+            .with_chunk_size(chunk_size)
+            .with_threads(threads)
+            .decode(&decoder, input.as_bytes(), Path::new("input"))
+            .unwrap();
+
+        prop_assert_eq!(expected, streamed);
+    }
+}
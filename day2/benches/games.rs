@@ -0,0 +1,200 @@
+//! Times parsing and solving a million generated games, once with the library's colour-indexed
+//! rounds and once with the rounds kept in a `HashMap`, as they used to be. The `HashMap`
+//! version below is the library's parsing and solving with only the round storage swapped, so
+//! the difference is what the colour-indexed rounds save.
+//!
+//! Run with `cargo bench -p day2-cube-conundrum --bench games`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc_common::{lines, ParseError, Rng, Solution};
+use day2::{Bag, Cube, Day2, Palette};
+
+const GAMES: usize = 1_000_000;
+const RUNS: usize = 3;
+
+/// A round with its counts kept in a `HashMap`, as they used to be.
+#[derive(Debug, Default)]
+struct HashMapRound {
+    cubes: HashMap<Cube, u64>,
+}
+
+impl HashMapRound {
+    fn set_count_for_cube(&mut self, cube: Cube, count: u64) {
+        self.cubes.insert(cube, count);
+    }
+
+    fn get_count_for_cube(&self, cube: &Cube) -> u64 {
+        *self.cubes.get(cube).unwrap_or(&0)
+    }
+
+    fn cubes(&self) -> impl Iterator<Item = (Cube, u64)> + '_ {
+        self.cubes
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(cube, count)| (*cube, *count))
+    }
+}
+
+/// [`day2::Game`], made of [`HashMapRound`]s.
+#[derive(Debug)]
+struct HashMapGame {
+    id: usize,
+    rounds: Vec<HashMapRound>,
+}
+
+impl HashMapGame {
+    fn max_seen_for_cube(&self, cube: Cube) -> u64 {
+        self.rounds
+            .iter()
+            .map(|round| round.get_count_for_cube(&cube))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn is_possible_with(&self, bag: &HashMapRound) -> bool {
+        self.rounds.iter().all(|round| {
+            round
+                .cubes()
+                .all(|(cube, count)| count <= bag.get_count_for_cube(&cube))
+        })
+    }
+
+    fn power(&self) -> u64 {
+        let mut colors = vec![Cube::RED, Cube::GREEN, Cube::BLUE];
+        colors.extend(
+            self.rounds
+                .iter()
+                .flat_map(|round| round.cubes())
+                .map(|(cube, _)| cube),
+        );
+        colors.sort();
+        colors.dedup();
+
+        colors
+            .into_iter()
+            .map(|cube| self.max_seen_for_cube(cube))
+            .product()
+    }
+}
+
+/// [`day2::read_games`], making [`HashMapGame`]s.
+fn read_hashmap_games(contents: &str) -> Result<(Palette, Vec<HashMapGame>), ParseError> {
+    let mut palette = Palette::new();
+    let mut games: Vec<HashMapGame> = vec![];
+
+    for line in lines(contents) {
+        let Some((header, rounds)) = line.text.split_once(':') else {
+            return Err(line.error(line.end(), "expected ':' after the game ID"));
+        };
+
+        let id = match header.strip_prefix("Game ") {
+            Some(id) => line.parse::<usize>(id.trim(), "a game ID")?,
+            None => return Err(line.error(header, "expected 'Game <ID>'")),
+        };
+
+        let mut game = HashMapGame { id, rounds: vec![] };
+
+        for round in rounds.split(";") {
+            let mut round_struct = HashMapRound::default();
+
+            for cube in round.split(",") {
+                let cube = cube.trim();
+                let (count, color) = cube.split_once(' ').unwrap_or((cube, &cube[cube.len()..]));
+
+                let count = line.parse::<u64>(count, "a number of cubes")?;
+
+                let color = color.trim();
+                if color.is_empty() {
+                    return Err(line.error(color, "expected a colour"));
+                }
+
+                round_struct.set_count_for_cube(palette.cube(color), count);
+            }
+
+            game.rounds.push(round_struct);
+        }
+
+        games.push(game);
+    }
+
+    Ok((palette, games))
+}
+
+fn hashmap_part1((palette, games): &(Palette, Vec<HashMapGame>)) -> u64 {
+    let mut bag = HashMapRound::default();
+    for (color, count) in Bag::puzzle().cubes() {
+        if let Some(cube) = palette.find(color) {
+            bag.set_count_for_cube(cube, count);
+        }
+    }
+
+    games
+        .iter()
+        .filter(|game| game.is_possible_with(&bag))
+        .map(|game| game.id as u64)
+        .sum()
+}
+
+fn hashmap_part2((_, games): &(Palette, Vec<HashMapGame>)) -> u64 {
+    games.iter().map(HashMapGame::power).sum()
+}
+
+/// Parses and solves `input` `RUNS` times, and prints the fastest run.
+fn measure<T>(
+    storage: &str,
+    input: &str,
+    parse: impl Fn(&str) -> T,
+    part1: impl Fn(&T) -> u64,
+    part2: impl Fn(&T) -> u64,
+) {
+    let mut times = vec![];
+    let mut answers = None;
+
+    for _ in 0..RUNS {
+        let start = Instant::now();
+
+        let record = parse(input);
+        let parsed = start.elapsed();
+
+        let answer = (part1(&record), part2(&record));
+        let solved = start.elapsed();
+
+        black_box(&record);
+        drop(record);
+
+        times.push((solved, parsed));
+        answers = Some(answer);
+    }
+
+    let (total, parse): (Duration, Duration) = times.into_iter().min().unwrap();
+    let (part1, part2) = answers.unwrap();
+
+    println!(
+        "{:<14} rounds: parse {:?}, parse and solve {:?} (part 1: {}, part 2: {})",
+        storage, parse, total, part1, part2
+    );
+}
+
+fn main() {
+    let input = day2::generate::generate(&mut Rng::new(1), GAMES);
+
+    println!("{} games, {} bytes", GAMES, input.len());
+
+    measure(
+        "colour-indexed",
+        &input,
+        |input| Day2::parse(input).unwrap(),
+        |record| Day2::part1(record).unwrap(),
+        |record| Day2::part2(record).unwrap(),
+    );
+    measure(
+        "HashMap",
+        &input,
+        |input| read_hashmap_games(input).unwrap(),
+        hashmap_part1,
+        hashmap_part2,
+    );
+}
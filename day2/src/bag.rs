@@ -0,0 +1,233 @@
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::{lines, ParseError};
+
+use crate::{Palette, Record, Round};
+
+/// What a bag holds, by colour name, like `red=12,green=13,blue=14`. Colours it doesn't name
+/// aren't in it at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    cubes: Vec<(String, u64)>,
+}
+
+impl Bag {
+    /// The bag part one of the puzzle asks about.
+    pub fn puzzle() -> Bag {
+        Bag {
+            cubes: vec![
+                ("red".to_string(), 12),
+                ("green".to_string(), 13),
+                ("blue".to_string(), 14),
+            ],
+        }
+    }
+
+    /// The bag holding the cubes shown in `round`, with the colours in the order of `palette`.
+    pub fn from_round(round: &Round, palette: &Palette) -> Bag {
+        let cubes = palette
+            .cubes()
+            .map(|cube| (palette.name(cube), round.get_count_for_cube(&cube)))
+            .filter(|(_, count)| *count > 0)
+            .map(|(color, count)| (color.to_string(), count))
+            .collect();
+
+        Bag { cubes }
+    }
+
+    /// Every colour in the bag and how many cubes of it there are.
+    pub fn cubes(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.cubes
+            .iter()
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+
+    /// The bag as a round showing every cube in it, for the colours of `palette`. Colours the
+    /// palette doesn't have are left out, as no game shows them anyway.
+    pub fn to_round(&self, palette: &Palette) -> Round {
+        let mut round = Round::default();
+
+        for (color, count) in self.cubes() {
+            if let Some(cube) = palette.find(color) {
+                round.set_count_for_cube(cube, count);
+            }
+        }
+
+        round
+    }
+}
+
+impl FromStr for Bag {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut cubes: Vec<(String, u64)> = vec![];
+
+        for cube in text.split(',') {
+            let Some((color, count)) = cube.split_once('=') else {
+                return Err(format!("expected '<colour>=<count>', not '{}'", cube));
+            };
+
+            let color = color.trim();
+            if color.is_empty() {
+                return Err(format!("'{}' has no colour", cube));
+            }
+            if cubes.iter().any(|(known, _)| known == color) {
+                return Err(format!("{} is in the bag twice", color));
+            }
+
+            let count = count
+                .trim()
+                .parse()
+                .or(Err(format!("'{}' is not a number of cubes", count)))?;
+
+            cubes.push((color.to_string(), count));
+        }
+
+        Ok(Bag { cubes })
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cubes: Vec<String> = self
+            .cubes()
+            .map(|(color, count)| format!("{}={}", color, count))
+            .collect();
+
+        write!(f, "{}", cubes.join(","))
+    }
+}
+
+/// Reads one bag per line, like `red=12,green=13,blue=14`. Lines starting with `#` are comments.
+pub fn read_bags(contents: &str) -> Result<Vec<Bag>, ParseError> {
+    lines(contents)
+        .filter(|line| !line.text.trim_start().starts_with('#'))
+        .map(|line| {
+            line.text
+                .parse()
+                .map_err(|message| line.error(line.text, message))
+        })
+        .collect()
+}
+
+/// The fewest cubes of each colour every game could have been played with, worked out once so
+/// that any number of bags can be checked against them.
+#[derive(Debug, Clone)]
+pub struct FewestCubes {
+    palette: Palette,
+    ids: Vec<usize>,
+    /// The fewest cubes for each game, one after the other, with a count for every colour of the
+    /// palette.
+    counts: Vec<u64>,
+}
+
+impl FewestCubes {
+    pub fn new(record: &Record) -> FewestCubes {
+        let colors = record.palette.len();
+        let mut counts = Vec::with_capacity(record.games.len() * colors);
+
+        for game in &record.games {
+            counts.extend(
+                record
+                    .palette
+                    .cubes()
+                    .map(|cube| game.max_seen_for_cube(cube)),
+            );
+        }
+
+        FewestCubes {
+            palette: record.palette.clone(),
+            ids: record.games.iter().map(|game| game.id()).collect(),
+            counts,
+        }
+    }
+
+    /// The IDs of the games that could have been played with `bag`.
+    pub fn possible_games(&self, bag: &Bag) -> Vec<usize> {
+        let bag: Vec<u64> = {
+            let round = bag.to_round(&self.palette);
+            self.palette
+                .cubes()
+                .map(|cube| round.get_count_for_cube(&cube))
+                .collect()
+        };
+
+        self.ids
+            .iter()
+            .zip(self.counts.chunks_exact(bag.len()))
+            .filter(|(_, fewest)| fewest.iter().zip(&bag).all(|(needed, held)| needed <= held))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// The IDs of the games that could have been played with each of `bags`, in the same order.
+    pub fn possible_games_for_bags(&self, bags: &[Bag]) -> Vec<Vec<usize>> {
+        bags.iter().map(|bag| self.possible_games(bag)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let bag: Bag = "red=12, green=13,blue=14".parse().unwrap();
+
+        assert_eq!(Bag::puzzle(), bag);
+        assert_eq!("red=12,green=13,blue=14", bag.to_string());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("red".parse::<Bag>().is_err());
+        assert!("red=x".parse::<Bag>().is_err());
+        assert!("=3".parse::<Bag>().is_err());
+        assert!("red=1,red=2".parse::<Bag>().is_err());
+    }
+
+    #[test]
+    fn test_to_round() {
+        let mut palette = Palette::new();
+        let yellow = palette.cube("yellow");
+
+        let bag: Bag = "yellow=2,red=1,purple=5".parse().unwrap();
+        let round = bag.to_round(&palette);
+
+        assert_eq!(2, round.get_count_for_cube(&yellow));
+        assert_eq!(1, round.get_count_for_cube(&crate::Cube::RED));
+        assert_eq!(0, round.get_count_for_cube(&crate::Cube::BLUE));
+
+        assert_eq!(
+            "red=1,yellow=2",
+            Bag::from_round(&round, &palette).to_string()
+        );
+    }
+
+    #[test]
+    fn test_read_bags() {
+        let bags = read_bags("# the puzzle's bag\nred=12,green=13,blue=14\n\nred=1\n").unwrap();
+        assert_eq!(vec![Bag::puzzle(), "red=1".parse().unwrap()], bags);
+
+        let error = read_bags("red=1\nred=one\n").unwrap_err();
+        assert_eq!((2, "red=one"), (error.line, error.snippet.as_str()));
+    }
+
+    #[test]
+    fn test_fewest_cubes() {
+        let record = crate::read_games(include_str!("../input/example.txt")).unwrap();
+        let fewest = FewestCubes::new(&record);
+
+        let bags = read_bags("red=12,green=13,blue=14\nred=20,green=13,blue=15\nred=4\n").unwrap();
+        let possible = fewest.possible_games_for_bags(&bags);
+
+        assert_eq!(vec![vec![1, 2, 5], vec![1, 2, 3, 4, 5], vec![]], possible);
+
+        for (bag, possible) in bags.iter().zip(possible) {
+            let round = bag.to_round(&record.palette);
+            assert_eq!(crate::get_possible_games(&record, &round), possible);
+        }
+    }
+}
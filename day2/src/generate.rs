@@ -0,0 +1,29 @@
+use aoc_common::Rng;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+/// Generates `size` games of up to 20 rounds each.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for id in 1..=size {
+        let rounds: Vec<String> = (0..rng.range(1, 20))
+            .map(|_| {
+                let mut colors = COLORS;
+                rng.shuffle(&mut colors);
+
+                let shown = rng.range(1, 3) as usize;
+                let cubes: Vec<String> = colors[..shown]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.range(1, 20), color))
+                    .collect();
+
+                cubes.join(", ")
+            })
+            .collect();
+
+        output.push_str(&format!("Game {}: {}\n", id, rounds.join("; ")));
+    }
+
+    output
+}
@@ -0,0 +1,379 @@
+pub mod bag;
+pub mod generate;
+pub mod likelihood;
+
+use aoc_common::{lines, Error, ParseError, Solution};
+use tracing::trace;
+
+pub use bag::{Bag, FewestCubes};
+
+pub struct Day2;
+
+/// Every game in the input, along with the colours the cubes in them come in.
+#[derive(Debug)]
+pub struct Record {
+    pub palette: Palette,
+    pub games: Vec<Game>,
+}
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Record;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(read_games(input)?)
+    }
+
+    fn part1(record: &Self::Input) -> Result<Self::Answer, Error> {
+        let bag = Bag::puzzle().to_round(&record.palette);
+
+        Ok(possible_id_sum(record, &bag))
+    }
+
+    fn part2(record: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(power_sum(record))
+    }
+}
+
+/// A colour of cube, as registered in a [`Palette`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Cube(usize);
+
+impl Cube {
+    pub const RED: Cube = Cube(0);
+    pub const GREEN: Cube = Cube(1);
+    pub const BLUE: Cube = Cube(2);
+
+    /// Where the colour is in its palette, starting at 0 for red.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// The colours cubes come in. Red, green and blue are always there, and any other colour is
+/// added the first time it is seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    names: Vec<String>,
+}
+
+impl Palette {
+    pub fn new() -> Palette {
+        Palette {
+            names: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+        }
+    }
+
+    /// The cube for the colour called `name`, adding the colour if it is new.
+    pub fn cube(&mut self, name: &str) -> Cube {
+        self.find(name).unwrap_or_else(|| {
+            self.names.push(name.to_string());
+            Cube(self.names.len() - 1)
+        })
+    }
+
+    /// Looks up a cube by the colour used for it in the input.
+    pub fn find(&self, name: &str) -> Option<Cube> {
+        self.names.iter().position(|known| known == name).map(Cube)
+    }
+
+    pub fn name(&self, cube: Cube) -> &str {
+        &self.names[cube.0]
+    }
+
+    /// Every colour, in the order they were added.
+    pub fn cubes(&self) -> impl Iterator<Item = Cube> {
+        (0..self.names.len()).map(Cube)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new()
+    }
+}
+
+/// How many colours a [`Round`] holds the counts of without allocating: red, green and blue.
+const INLINE_COLORS: usize = 3;
+
+/// How many cubes of each colour were shown at once.
+///
+/// The counts are kept by [`Cube::index`], red, green and blue in the round itself and any other
+/// colours after them on the heap.
+#[derive(Debug, Clone, Default)]
+pub struct Round {
+    counts: [u64; INLINE_COLORS],
+    more_counts: Vec<u64>,
+}
+
+impl Round {
+    pub fn new(red: u64, green: u64, blue: u64) -> Round {
+        Round {
+            counts: [red, green, blue],
+            more_counts: vec![],
+        }
+    }
+
+    pub fn set_count_for_cube(&mut self, cube: Cube, count: u64) {
+        match cube.index().checked_sub(INLINE_COLORS) {
+            None => self.counts[cube.index()] = count,
+            Some(index) => {
+                if index >= self.more_counts.len() {
+                    self.more_counts.resize(index + 1, 0);
+                }
+
+                self.more_counts[index] = count;
+            }
+        }
+    }
+
+    pub fn get_count_for_cube(&self, cube: &Cube) -> u64 {
+        match cube.index().checked_sub(INLINE_COLORS) {
+            None => self.counts[cube.index()],
+            Some(index) => self.more_counts.get(index).copied().unwrap_or(0),
+        }
+    }
+
+    /// Every colour shown in this round, and how many cubes of it, in no particular order.
+    pub fn cubes(&self) -> impl Iterator<Item = (Cube, u64)> + '_ {
+        self.counts
+            .iter()
+            .chain(&self.more_counts)
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(index, count)| (Cube(index), *count))
+    }
+}
+
+/// Every round shown from the bag during one game.
+#[derive(Debug)]
+pub struct Game {
+    id: usize,
+    rounds: Vec<Round>,
+}
+
+impl Game {
+    pub fn new(id: usize) -> Game {
+        Game { id, rounds: vec![] }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    pub fn add_round(&mut self, round: Round) {
+        self.rounds.push(round);
+    }
+
+    /// The most cubes of a colour shown in any round, which is also the fewest the bag can hold.
+    pub fn max_seen_for_cube(&self, cube: Cube) -> u64 {
+        self.rounds
+            .iter()
+            .map(|round| round.get_count_for_cube(&cube))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Whether every round could have been shown from a bag holding the cubes in `bag`. Colours
+    /// the bag doesn't mention aren't in it at all.
+    pub fn is_possible_with(&self, bag: &Round) -> bool {
+        self.rounds.iter().all(|round| {
+            round
+                .cubes()
+                .all(|(cube, count)| count <= bag.get_count_for_cube(&cube))
+        })
+    }
+
+    /// The product of the fewest cubes of red, green, blue and any other colour this game shows
+    /// that the bag could have held. Colours only other games show don't count.
+    pub fn power(&self) -> u64 {
+        let mut colors = vec![Cube::RED, Cube::GREEN, Cube::BLUE];
+        colors.extend(
+            self.rounds
+                .iter()
+                .flat_map(|round| round.cubes())
+                .map(|(cube, _)| cube),
+        );
+        colors.sort();
+        colors.dedup();
+
+        colors
+            .into_iter()
+            .map(|cube| self.max_seen_for_cube(cube))
+            .product()
+    }
+}
+
+/// Reads one game per line, like `Game 1: 3 blue, 4 red; 1 red, 2 green`. Cubes can be of any
+/// colour.
+pub fn read_games(contents: &str) -> Result<Record, ParseError> {
+    let mut palette = Palette::new();
+    let mut games: Vec<Game> = vec![];
+
+    for line in lines(contents) {
+        let Some((header, rounds)) = line.text.split_once(':') else {
+            return Err(line.error(line.end(), "expected ':' after the game ID"));
+        };
+
+        let game_id = match header.strip_prefix("Game ") {
+            Some(id) => line.parse::<usize>(id.trim(), "a game ID")?,
+            None => return Err(line.error(header, "expected 'Game <ID>'")),
+        };
+
+        let mut game = Game::new(game_id);
+
+        for round in rounds.split(";") {
+            let cubes = round.split(",");
+
+            let mut round_struct = Round::default();
+
+            for cube in cubes {
+                let cube = cube.trim();
+                let (count, color) = cube.split_once(' ').unwrap_or((cube, &cube[cube.len()..]));
+
+                let count = line.parse::<u64>(count, "a number of cubes")?;
+
+                let color = color.trim();
+                if color.is_empty() {
+                    return Err(line.error(color, "expected a colour"));
+                }
+
+                round_struct.set_count_for_cube(palette.cube(color), count);
+            }
+
+            game.add_round(round_struct);
+        }
+
+        games.push(game);
+    }
+
+    Ok(Record { palette, games })
+}
+
+/// The most cubes of each colour of `palette` shown in any round of `game`, like
+/// `red=4,green=2,blue=6`, for the logs.
+fn maxima(game: &Game, palette: &Palette) -> String {
+    let maxima: Vec<String> = palette
+        .cubes()
+        .map(|cube| format!("{}={}", palette.name(cube), game.max_seen_for_cube(cube)))
+        .collect();
+
+    maxima.join(",")
+}
+
+/// Finds the IDs of the games that could have been played with a bag holding the cubes in
+/// `bag`.
+pub fn get_possible_games(record: &Record, bag: &Round) -> Vec<usize> {
+    let mut possible_games: Vec<usize> = vec![];
+
+    for game in &record.games {
+        trace!(
+            game = game.id,
+            maxima = %maxima(game, &record.palette),
+            "fewest cubes needed"
+        );
+
+        if game.is_possible_with(bag) {
+            possible_games.push(game.id);
+        }
+    }
+
+    possible_games
+}
+
+/// The sum of the IDs of the games that could have been played with `bag`, which is the answer
+/// to part one for the puzzle's bag.
+pub fn possible_id_sum(record: &Record, bag: &Round) -> u64 {
+    get_possible_games(record, bag)
+        .into_iter()
+        .map(|id| id as u64)
+        .sum()
+}
+
+/// The sum of the powers of every game, which is the answer to part two.
+pub fn power_sum(record: &Record) -> u64 {
+    record.games.iter().map(Game::power).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn test_example() {
+        let record = read_games(EXAMPLE).unwrap();
+
+        assert_eq!(3, record.palette.len());
+        assert_eq!(
+            vec![1, 2, 5],
+            get_possible_games(&record, &Round::new(12, 13, 14))
+        );
+        assert_eq!(48, record.games[0].power());
+
+        assert_eq!(8, possible_id_sum(&record, &Round::new(12, 13, 14)));
+        assert_eq!(2286, power_sum(&record));
+    }
+
+    #[test]
+    fn test_other_colours() {
+        let record = read_games(
+            "Game 1: 2 yellow, 1 red; 3 green, 1 blue
+Game 2: 4 red, 5 green, 6 blue
+",
+        )
+        .unwrap();
+
+        let yellow = record.palette.find("yellow").unwrap();
+        assert_eq!("yellow", record.palette.name(yellow));
+        assert_eq!(2, record.games[0].max_seen_for_cube(yellow));
+
+        // Only game 1 shows a yellow cube, so only its power counts yellow.
+        let powers: Vec<u64> = record.games.iter().map(Game::power).collect();
+        assert_eq!(vec![6, 120], powers);
+
+        let mut bag = Round::new(12, 13, 14);
+        assert_eq!(vec![2], get_possible_games(&record, &bag));
+
+        bag.set_count_for_cube(yellow, 2);
+        assert_eq!(vec![1, 2], get_possible_games(&record, &bag));
+    }
+
+    #[test]
+    fn test_round() {
+        let mut palette = Palette::new();
+        let yellow = palette.cube("yellow");
+        let purple = palette.cube("purple");
+
+        let mut round = Round::new(1, 0, 3);
+        round.set_count_for_cube(purple, 5);
+        round.set_count_for_cube(Cube::RED, 2);
+
+        assert_eq!(0, round.get_count_for_cube(&yellow));
+        assert_eq!(5, round.get_count_for_cube(&purple));
+        let mut cubes: Vec<(Cube, u64)> = round.cubes().collect();
+        cubes.sort();
+        assert_eq!(vec![(Cube::RED, 2), (Cube::BLUE, 3), (purple, 5)], cubes);
+    }
+
+    #[test]
+    fn test_missing_colour() {
+        let error = read_games("Game 1: 3 blue, 4").unwrap_err();
+        assert_eq!((1, 18), (error.line, error.column));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{Cube, Game, Round};
+
+/// The most likely contents of the bag a game was played with.
+#[derive(Debug, Clone)]
+pub struct Estimate {
+    pub bag: Round,
+    /// The natural log of the chance of the game's rounds being shown from the bag.
+    pub log_likelihood: f64,
+    /// Whether the bag holds as many cubes of some colour as the search allowed, so that a
+    /// likelier bag might have been found with a higher bound.
+    pub at_bound: bool,
+}
+
+impl Estimate {
+    pub fn likelihood(&self) -> f64 {
+        self.log_likelihood.exp()
+    }
+}
+
+/// The natural log of the number of ways to pick `k` things out of `n`.
+fn ln_choose(n: u64, k: u64) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+
+    let k = k.min(n - k);
+    (1..=k)
+        .map(|i| ((n - k + i) as f64).ln() - (i as f64).ln())
+        .sum()
+}
+
+/// The natural log of the chance of drawing the cubes in `round` out of `bag`, all at once and
+/// without putting any back. Negative infinity when the bag doesn't hold enough cubes.
+pub fn round_log_likelihood(round: &Round, bag: &Round) -> f64 {
+    let mut drawn = 0;
+    let mut ways = 0.0;
+
+    for (cube, count) in round.cubes() {
+        let held = bag.get_count_for_cube(&cube);
+
+        // Checked here, as the chance would otherwise come out as -inf minus -inf.
+        if count > held {
+            return f64::NEG_INFINITY;
+        }
+
+        drawn += count;
+        ways += ln_choose(held, count);
+    }
+
+    let total = bag.cubes().map(|(_, count)| count).sum();
+
+    ways - ln_choose(total, drawn)
+}
+
+/// The natural log of the chance of the game's rounds being shown from `bag`, with the cubes
+/// put back after each round.
+pub fn log_likelihood(game: &Game, bag: &Round) -> f64 {
+    game.rounds()
+        .iter()
+        .map(|round| round_log_likelihood(round, bag))
+        .sum()
+}
+
+/// Tries every bag holding up to `bound` cubes of each colour the game shows, and none of any
+/// other colour, and returns the one the game was most likely played with. Of equally likely
+/// bags, the one with the fewest cubes of the first colours wins.
+///
+/// The search tries up to `bound ^ colours` bags, so it is only quick for a handful of colours
+/// and small bounds. The estimate is only a true maximum when [`Estimate::at_bound`] is false.
+/// `None` when the game shows more than `bound` cubes of a colour.
+pub fn most_likely_bag(game: &Game, bound: u64) -> Option<Estimate> {
+    let mut colors: Vec<Cube> = game
+        .rounds()
+        .iter()
+        .flat_map(|round| round.cubes())
+        .filter(|(_, count)| *count > 0)
+        .map(|(cube, _)| cube)
+        .collect();
+    colors.sort();
+    colors.dedup();
+
+    let fewest: Vec<u64> = colors
+        .iter()
+        .map(|cube| game.max_seen_for_cube(*cube))
+        .collect();
+
+    if fewest.iter().any(|count| *count > bound) {
+        return None;
+    }
+
+    let mut counts = fewest.clone();
+    let mut best: Option<Estimate> = None;
+
+    loop {
+        let mut bag = Round::default();
+        for (cube, count) in colors.iter().zip(&counts) {
+            bag.set_count_for_cube(*cube, *count);
+        }
+
+        let log_likelihood = log_likelihood(game, &bag);
+        if best
+            .as_ref()
+            .is_none_or(|best| log_likelihood > best.log_likelihood)
+        {
+            best = Some(Estimate {
+                bag,
+                log_likelihood,
+                at_bound: counts.contains(&bound),
+            });
+        }
+
+        // Counts up like an odometer, with the last colour turning fastest.
+        let Some(index) = (0..counts.len()).rev().find(|&index| counts[index] < bound) else {
+            break;
+        };
+
+        counts[index] += 1;
+        counts[index + 1..].copy_from_slice(&fewest[index + 1..]);
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_games;
+
+    fn close(expected: f64, actual: f64) -> bool {
+        (expected - actual).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_round_likelihood() {
+        let bag = Round::new(3, 0, 1);
+
+        assert!(close(
+            0.75,
+            round_log_likelihood(&Round::new(1, 0, 0), &bag).exp()
+        ));
+        assert!(close(
+            1.0,
+            round_log_likelihood(&Round::new(3, 0, 1), &bag).exp()
+        ));
+        assert!(close(
+            0.5,
+            round_log_likelihood(&Round::new(1, 0, 1), &bag).exp()
+        ));
+        assert_eq!(
+            f64::NEG_INFINITY,
+            round_log_likelihood(&Round::new(0, 1, 0), &bag)
+        );
+    }
+
+    #[test]
+    fn test_more_cubes_than_the_bag() {
+        let bag = Round::new(3, 0, 1);
+        assert_eq!(
+            f64::NEG_INFINITY,
+            round_log_likelihood(&Round::new(5, 0, 0), &bag)
+        );
+
+        let record = read_games("Game 1: 5 red; 1 blue\n").unwrap();
+        assert_eq!(f64::NEG_INFINITY, log_likelihood(&record.games[0], &bag));
+    }
+
+    #[test]
+    fn test_game_likelihood() {
+        let record = read_games("Game 1: 1 red; 1 blue\n").unwrap();
+        let game = &record.games[0];
+
+        // Each round has an even chance from a bag with one red and one blue cube.
+        assert!(close(
+            0.25,
+            log_likelihood(game, &Round::new(1, 0, 1)).exp()
+        ));
+        assert!(close(
+            1.0 / 9.0,
+            log_likelihood(game, &Round::new(1, 1, 1)).exp()
+        ));
+    }
+
+    #[test]
+    fn test_most_likely_bag() {
+        let record = read_games("Game 1: 3 red; 2 red, 1 blue; 1 blue\nGame 2: 9 green\n").unwrap();
+
+        // A fourth red cube makes the second and third rounds less likely, but the first one
+        // more likely by more: 4/10 * 6/10 * 1/5 against 1/4 * 3/4 * 1/4.
+        let estimate = most_likely_bag(&record.games[0], 10).unwrap();
+        let bag = &estimate.bag;
+        assert!(close(0.048, estimate.likelihood()));
+        assert!(!estimate.at_bound);
+        assert_eq!(
+            (4, 0, 1),
+            (
+                bag.get_count_for_cube(&Cube::RED),
+                bag.get_count_for_cube(&Cube::GREEN),
+                bag.get_count_for_cube(&Cube::BLUE)
+            )
+        );
+
+        // No bag does better than the most likely one.
+        for red in 3..=10 {
+            for blue in 1..=10 {
+                let other = log_likelihood(&record.games[0], &Round::new(red, 0, blue));
+                assert!(other <= estimate.log_likelihood);
+            }
+        }
+
+        assert!(most_likely_bag(&record.games[1], 8).is_none());
+
+        // The bag with four red cubes is out of reach, so the search stops at three.
+        assert!(most_likely_bag(&record.games[0], 3).unwrap().at_bound);
+    }
+}
@@ -1,153 +1,162 @@
-use std::collections::HashMap;
-
-#[derive(Debug, Eq, PartialEq, Hash)]
-enum Cube {
-    Red,
-    Green,
-    Blue,
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc_common::{Error, Part};
+use day2::{bag, likelihood, possible_id_sum, power_sum, read_games, Bag, FewestCubes};
+
+const USAGE: &str = "\
+usage: day2-cube-conundrum <INPUT> [--part <1|2>] [--bag <COLOUR=COUNT,...>]
+       day2-cube-conundrum <INPUT> --bags <FILE>
+       day2-cube-conundrum <INPUT> --most-likely <BOUND>
+
+Part 1 adds up the IDs of the games that could have been played with the bag, which holds
+red=12,green=13,blue=14 unless --bag says otherwise. Colours --bag leaves out aren't in the
+bag at all.
+Part 2 adds up the powers of the fewest cubes each game could have been played with.
+--bags lists the games that could have been played with each bag in the file, which has one
+bag per line, written like --bag.
+--most-likely finds the bag each game was most likely played with, if the cubes of each round
+were drawn at random and put back afterwards, trying up to BOUND cubes of every colour. That
+is up to BOUND^colours bags per game. Bags marked 'at the bound' hold BOUND cubes of some
+colour, so a higher bound might find a likelier one.";
+
+struct Args {
+    input: PathBuf,
+    parts: Vec<Part>,
+    bag: Bag,
+    bags: Option<PathBuf>,
+    most_likely: Option<u64>,
 }
 
-impl Cube {
-    fn from_str(cube: &str) -> Cube {
-        match cube {
-            "red" => Cube::Red,
-            "green" => Cube::Green,
-            "blue" => Cube::Blue,
-            _ => panic!("what the hell is wrong with you?"),
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut input: Option<PathBuf> = None;
+    let mut parts: Option<Vec<Part>> = None;
+    let mut bag: Option<Bag> = None;
+    let mut bags: Option<PathBuf> = None;
+    let mut most_likely: Option<u64> = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--part" => {
+                parts = match value()?.as_str() {
+                    "1" => Some(vec![Part::One]),
+                    "2" => Some(vec![Part::Two]),
+                    other => return Err(format!("there is no part '{}'", other)),
+                };
+            }
+            "--bag" => bag = Some(value()?.parse()?),
+            "--bags" => bags = Some(PathBuf::from(value()?)),
+            "--most-likely" => {
+                let value = value()?;
+                most_likely = Some(
+                    value
+                        .parse()
+                        .or(Err(format!("'{}' is not a number of cubes", value)))?,
+                );
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown argument '{}'", arg)),
+            _ if input.is_none() => input = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
         }
     }
-}
 
-#[derive(Debug)]
-struct Round {
-    cubes: HashMap<Cube, u64>,
-}
-
-impl Round {
-    fn new(red: u64, green: u64, blue: u64) -> Round {
-        let cubes = HashMap::from([(Cube::Red, red), (Cube::Green, green), (Cube::Blue, blue)]);
-
-        Round { cubes }
+    if bag.is_some() && bags.is_some() {
+        return Err("--bag can't be used with --bags".to_string());
     }
-
-    fn set_count_for_cube(&mut self, cube: Cube, count: u64) {
-        self.cubes.insert(cube, count);
+    if parts.is_some() && bags.is_some() {
+        return Err("--part can't be used with --bags".to_string());
     }
-
-    fn get_count_for_cube(&self, cube: &Cube) -> u64 {
-        self.cubes.get(&cube).unwrap_or(&0).clone()
+    if parts.is_some() && most_likely.is_some() {
+        return Err("--part can't be used with --most-likely".to_string());
     }
-}
-
-#[derive(Debug)]
-struct Game {
-    id: usize,
-    rounds: Vec<Round>,
-}
-
-impl Game {
-    fn new(id: usize) -> Game {
-        Game {
-            id: id,
-            rounds: vec![],
-        }
+    if most_likely.is_some() && (bag.is_some() || bags.is_some()) {
+        return Err("--most-likely can't be used with --bag or --bags".to_string());
     }
 
-    fn add_round(&mut self, round: Round) {
-        self.rounds.push(round);
-    }
-
-    fn max_seen_for_cube(&self, cube: Cube) -> u64 {
-        self.rounds
-            .iter()
-            .map(|round| round.get_count_for_cube(&cube))
-            .max()
-            .unwrap()
-    }
+    Ok(Args {
+        input: input.ok_or("no input file given")?,
+        parts: parts.unwrap_or(vec![Part::One, Part::Two]),
+        bag: bag.unwrap_or_else(Bag::puzzle),
+        bags,
+        most_likely,
+    })
 }
 
-fn read_games_from_string(contents: String) -> Vec<Game> {
-    let mut games: Vec<Game> = vec![];
-
-    for line in contents.lines() {
-        let mut line = line.split(":");
+fn run(args: Args) -> Result<(), Error> {
+    let input = aoc_common::read_input(&args.input)?;
+    let record = read_games(&input).map_err(|error| Error::from(error).with_file(&args.input))?;
 
-        let game_id = line
-            .next()
-            .unwrap()
-            .split(" ")
-            .nth(1)
-            .unwrap()
-            .parse::<usize>()
-            .unwrap();
+    if let Some(path) = &args.bags {
+        let bags = aoc_common::read_input(path)?;
+        let bags = bag::read_bags(&bags).map_err(|error| Error::from(error).with_file(path))?;
 
-        let mut game = Game::new(game_id);
+        let fewest = FewestCubes::new(&record);
 
-        for round in line.next().unwrap().split(";") {
-            let cubes = round.split(",");
+        for (bag, games) in bags.iter().zip(fewest.possible_games_for_bags(&bags)) {
+            let ids: Vec<String> = games.iter().map(usize::to_string).collect();
+            let sum: usize = games.iter().sum();
 
-            let mut round_struct = Round::new(0, 0, 0);
-
-            for cube in cubes {
-                let mut thing = cube.trim().split(" ");
-
-                let count = thing.next().unwrap().parse::<u64>().unwrap();
-
-                let color: &str = thing.next().unwrap();
+            println!("{}: {} ({})", bag, sum, ids.join(", "));
+        }
 
-                let color_struct = match color {
-                    "red" => Cube::Red,
-                    "green" => Cube::Green,
-                    "blue" => Cube::Blue,
-                    _ => panic!("that shouldn't happen"),
-                };
+        return Ok(());
+    }
 
-                round_struct.set_count_for_cube(color_struct, count);
+    if let Some(bound) = args.most_likely {
+        for game in &record.games {
+            match likelihood::most_likely_bag(game, bound) {
+                Some(estimate) => println!(
+                    "Game {}: {} (likelihood {:.3e}{})",
+                    game.id(),
+                    Bag::from_round(&estimate.bag, &record.palette),
+                    estimate.likelihood(),
+                    if estimate.at_bound {
+                        ", at the bound"
+                    } else {
+                        ""
+                    }
+                ),
+                None => println!(
+                    "Game {}: needs more than {} cubes of a colour",
+                    game.id(),
+                    bound
+                ),
             }
-
-            game.add_round(round_struct);
         }
 
-        games.push(game);
+        return Ok(());
     }
 
-    games
-}
-
-fn get_possible_games(games: &Vec<Game>, red: u64, green: u64, blue: u64) -> Vec<usize> {
-    let mut possible_games: Vec<usize> = vec![];
+    for part in args.parts {
+        let answer = match part {
+            Part::One => possible_id_sum(&record, &args.bag.to_round(&record.palette)),
+            Part::Two => power_sum(&record),
+        };
 
-    for game in games {
-        let max_red = game.max_seen_for_cube(Cube::Red);
-        let max_green = game.max_seen_for_cube(Cube::Green);
-        let max_blue = game.max_seen_for_cube(Cube::Blue);
-
-        if max_red <= red && max_green <= green && max_blue <= blue {
-            possible_games.push(game.id);
-        }
+        println!("Part {}: {}", part.number(), answer);
     }
 
-    possible_games
+    Ok(())
 }
 
-fn main() {
-    let file_path = std::env::args().nth(1).unwrap();
-
-    let games = read_games_from_string(std::fs::read_to_string(file_path).unwrap());
-
-    let mut sum: u64 = 0;
-
-    for game in games {
-        let min_possible_red = game.max_seen_for_cube(Cube::Red);
-        let min_possible_green = game.max_seen_for_cube(Cube::Green);
-        let min_possible_blue = game.max_seen_for_cube(Cube::Blue);
+fn main() -> ExitCode {
+    aoc_common::init_logging(false);
 
-        dbg!(min_possible_red, min_possible_green, min_possible_blue);
-
-        let power = min_possible_red * min_possible_green * min_possible_blue;
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
 
-        sum += power;
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error.render());
+            ExitCode::FAILURE
+        }
     }
-
-    println!("The answer to part two is {}", sum);
 }
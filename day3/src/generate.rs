@@ -0,0 +1,35 @@
+use aoc_common::Rng;
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+
+/// Generates a `size` by `size` schematic of numbers, gears and other symbols.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for _ in 0..size {
+        let mut row = String::new();
+
+        while row.len() < size {
+            let left = size - row.len();
+
+            match rng.range(0, 9) {
+                // Numbers are followed by a dot so that two of them never run together.
+                0 | 1 if left >= 2 => {
+                    let digits = rng.range(1, 3.min(left as u64 - 1));
+                    let number =
+                        rng.range(10u64.pow(digits as u32 - 1), 10u64.pow(digits as u32) - 1);
+
+                    row.push_str(&number.to_string());
+                    row.push('.');
+                }
+                2 => row.push(*rng.pick(&SYMBOLS)),
+                _ => row.push('.'),
+            }
+        }
+
+        output.push_str(&row);
+        output.push('\n');
+    }
+
+    output
+}
@@ -0,0 +1,257 @@
+pub mod generate;
+
+use std::collections::HashSet;
+
+use aoc_common::{lines, Error, ParseError, Solution};
+use regex::Regex;
+use tracing::trace;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Map;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(Map::parse(input)?)
+    }
+
+    fn part1(map: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(map.calculate_sum_of_part_numbers())
+    }
+
+    fn part2(map: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(map.calculate_sum_of_symbol_neighbors())
+    }
+}
+
+/// What is at a position of the engine schematic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Entry {
+    /// One digit of a number. Every digit of a number holds the whole number.
+    Number(Number),
+    /// A `*`, which might be a gear.
+    Gear,
+    /// Any other symbol.
+    Symbol,
+    Empty,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Number {
+    /// Tells apart numbers with the same value.
+    pub index: usize,
+    pub value: u64,
+}
+
+fn find_neighbors(map: &[Vec<Entry>], x: usize, y: usize) -> Option<HashSet<Number>> {
+    let neighbors = find_all_neighbors(map, x, y);
+
+    match neighbors.len() {
+        2 => Some(neighbors),
+        _ => None,
+    }
+}
+
+fn find_all_neighbors(map: &[Vec<Entry>], x: usize, y: usize) -> HashSet<Number> {
+    let mut neighbors: HashSet<Number> = HashSet::new();
+
+    let x = x as u64;
+    let y = y as u64;
+
+    for i in x.saturating_sub(1)..x + 2 {
+        for j in y.saturating_sub(1)..y + 2 {
+            if i == x && j == y {
+                continue;
+            }
+
+            if let Some(r) = map.get(i as usize) {
+                if let Some(Entry::Number(number)) = r.get(j as usize) {
+                    neighbors.insert(*number);
+                }
+            }
+        }
+    }
+
+    neighbors
+}
+
+/// The engine schematic, indexed by row and then column.
+#[derive(Debug, Default)]
+pub struct Map {
+    map: Vec<Vec<Entry>>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Map { map: vec![] }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Entry {
+        self.map
+            .get(x)
+            .and_then(|row| row.get(y))
+            .copied()
+            .unwrap_or(Entry::Empty)
+    }
+
+    pub fn set_position(&mut self, x: usize, y: usize, entry: Entry) {
+        if x >= self.map.len() {
+            self.map.resize(x + 1, vec![]);
+        }
+
+        let row = self.map.get_mut(x).unwrap();
+
+        if y >= row.len() {
+            row.resize(y + 1, Entry::Empty);
+        }
+
+        row[y] = entry;
+    }
+
+    /// Adds up every number next to a symbol, diagonals included.
+    pub fn calculate_sum_of_part_numbers(&self) -> u64 {
+        let mut found: HashSet<Number> = HashSet::new();
+
+        for (i, row) in self.map.iter().enumerate() {
+            for (j, val) in row.iter().enumerate() {
+                if let Entry::Gear | Entry::Symbol = val {
+                    found.extend(find_all_neighbors(&self.map, i, j));
+                }
+            }
+        }
+
+        found.iter().map(|number| number.value).sum()
+    }
+
+    /// Adds up the gear ratios: the product of the two numbers next to a `*` that has exactly two.
+    pub fn calculate_sum_of_symbol_neighbors(&self) -> u64 {
+        let mut found: HashSet<(Number, Number)> = HashSet::new();
+
+        for (i, row) in self.map.iter().enumerate() {
+            for (j, val) in row.iter().enumerate() {
+                if let Entry::Gear = val {
+                    if let Some(neighbors) = find_neighbors(&self.map, i, j) {
+                        let mut neighbors = neighbors.iter();
+
+                        let tup = (*neighbors.next().unwrap(), *neighbors.next().unwrap());
+
+                        found.insert(tup);
+                    }
+                }
+            }
+        }
+
+        found
+            .iter()
+            .map(|element| element.0.value * element.1.value)
+            .sum()
+    }
+
+    /// Reads the schematic, one row per line. A blank line is a row with nothing on it.
+    pub fn parse(contents: &str) -> Result<Map, ParseError> {
+        let width = lines(contents).next().map_or(0, |line| line.text.len());
+
+        // Every row has to be as long as the first one, or the columns wouldn't line up.
+        for line in lines(contents) {
+            if line.text.len() != width {
+                let message = format!("expected a row of {} characters", width);
+                return Err(line.error(line.text, message));
+            }
+        }
+
+        let mut map = Map::new();
+
+        let mut number_index = 0;
+
+        let re = Regex::new(r"(\d+)|([^.\d\s])").unwrap();
+        for line in lines(contents) {
+            let x = line.number - 1;
+
+            for capture in re.captures_iter(line.text) {
+                if let Some(number) = capture.get(1) {
+                    let entry = Entry::Number(Number {
+                        index: number_index,
+                        value: number
+                            .as_str()
+                            .parse::<u64>()
+                            .map_err(|_| line.error(number.as_str(), "number is too large"))?,
+                    });
+                    number_index += 1;
+
+                    for y in number.range() {
+                        map.set_position(x, y, entry);
+                    }
+                }
+
+                if let Some(symbol) = capture.get(2) {
+                    let entry = match symbol.as_str() {
+                        "*" => Entry::Gear,
+                        _ => Entry::Symbol,
+                    };
+
+                    map.set_position(x, symbol.start(), entry);
+                }
+            }
+        }
+
+        trace!(
+            rows = map.map.len(),
+            numbers = number_index,
+            "read the schematic"
+        );
+
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let map = Map::parse("467.\n..*.\n").unwrap();
+
+        let number = Entry::Number(Number {
+            index: 0,
+            value: 467,
+        });
+        assert_eq!(number, map.get(0, 2));
+        assert_eq!(Entry::Gear, map.get(1, 2));
+        assert_eq!(Entry::Empty, map.get(1, 3));
+    }
+
+    #[test]
+    fn test_parse_uneven_rows() {
+        let error = Map::parse("467.\n..*\n").unwrap_err();
+
+        assert_eq!((2, 1), (error.line, error.column));
+    }
+
+    #[test]
+    fn test_parse_blank_lines() {
+        // A blank line is an empty row, so it moves the rows after it down.
+        let map = Map::parse("\n467.\n..*.\n").unwrap();
+        assert_eq!(467, map.calculate_sum_of_part_numbers());
+
+        let map = Map::parse("467..114..\n\n...*......\n").unwrap();
+        assert_eq!(0, map.calculate_sum_of_part_numbers());
+    }
+
+    #[test]
+    fn test_parse_crlf() {
+        let map = Map::parse("467.\r\n..*.\r\n.35.\r\n").unwrap();
+
+        assert_eq!(Entry::Gear, map.get(1, 2));
+        assert_eq!(
+            Entry::Number(Number {
+                index: 1,
+                value: 35,
+            }),
+            map.get(2, 1)
+        );
+    }
+}
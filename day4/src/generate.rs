@@ -0,0 +1,59 @@
+use aoc_common::Rng;
+
+const WINNING_NUMBERS: usize = 10;
+const NUMBERS: usize = 25;
+
+/// Part two counts every copy won, which doubles with each card in a long enough cascade. The
+/// generated cards never win more copies than this in total.
+const MAX_COPIES: u64 = 1 << 48;
+
+/// Generates `size` cards, many of which win copies of the cards after them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    // The copies of each card won so far, as part two counts them.
+    let mut copies = vec![1u64; size];
+    let mut total = size as u64;
+
+    for index in 0..size {
+        let mut matches = rng.range(0, WINNING_NUMBERS as u64) as usize;
+        matches = matches.min(size - index - 1);
+
+        // Cut the cascade short rather than overflow.
+        let won = copies[index];
+        while matches > 0 && total + won * matches as u64 > MAX_COPIES {
+            matches -= 1;
+        }
+
+        for next in &mut copies[index + 1..=index + matches] {
+            *next += won;
+        }
+        total += won * matches as u64;
+
+        let mut pool: Vec<u64> = (1..=99).collect();
+        rng.shuffle(&mut pool);
+
+        // The first `matches` numbers are in both lists, the rest in only one of them.
+        let winning_numbers = &pool[..WINNING_NUMBERS];
+        let mut numbers: Vec<u64> = pool[..matches].to_vec();
+        numbers.extend(&pool[WINNING_NUMBERS..WINNING_NUMBERS + NUMBERS - matches]);
+        rng.shuffle(&mut numbers);
+
+        let format = |numbers: &[u64]| {
+            numbers
+                .iter()
+                .map(|number| format!("{:>2}", number))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        output.push_str(&format!(
+            "Card {:>3}: {} | {}\n",
+            index + 1,
+            format(winning_numbers),
+            format(&numbers)
+        ));
+    }
+
+    output
+}
@@ -0,0 +1,153 @@
+pub mod generate;
+
+// Line by line... AGAIN!
+// Load the card numbers, then the winning numbers
+// do the calculation
+
+use aoc_common::{lines, Error, ParseError, Solution};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Card>;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(read_cards(input)?)
+    }
+
+    fn part1(cards: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(calculate_winning_amount(cards))
+    }
+
+    fn part2(cards: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(calculate_total_cards(cards))
+    }
+}
+
+/// A scratchcard: the numbers you have, and the numbers that win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    id: u64,
+    numbers: Vec<u64>,
+    winning_numbers: Vec<u64>,
+}
+
+impl Card {
+    pub fn new(id: u64, numbers: Vec<u64>, winning_numbers: Vec<u64>) -> Self {
+        Card {
+            id,
+            numbers,
+            winning_numbers,
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// How many of your numbers are winning numbers.
+    pub fn matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|number| self.winning_numbers.contains(number))
+            .count()
+    }
+}
+
+/// Adds up the points of every card: one for the first match, doubled for every match after it.
+pub fn calculate_winning_amount(cards: &[Card]) -> u64 {
+    let mut total = 0;
+
+    for card in cards {
+        let matches = card.matches() as u32;
+
+        if matches > 0 {
+            total += u64::pow(2, matches - 1);
+        }
+    }
+
+    total
+}
+
+/// Counts the cards you end up with once every card has won its copies.
+pub fn calculate_total_cards(cards: &[Card]) -> u64 {
+    // Start with one copy of every card. Each copy of a card wins one copy of each of the next
+    // `matches` cards, so the copies can be counted in a single pass instead of cloning cards.
+    let mut copies: Vec<u64> = vec![1; cards.len()];
+
+    for (index, card) in cards.iter().enumerate() {
+        // Card IDs start at one, so the ID is also the index of the next card.
+        let first = card.id as usize;
+        let won = first..(first + card.matches()).min(cards.len());
+
+        for next in won {
+            copies[next] += copies[index];
+        }
+    }
+
+    copies.iter().sum()
+}
+
+/// Reads one card per line. The cards have to be numbered from 1 in order.
+pub fn read_cards(contents: &str) -> Result<Vec<Card>, ParseError> {
+    let mut cards: Vec<Card> = vec![];
+
+    for line in lines(contents) {
+        let Some((header, numbers)) = line.text.split_once(':') else {
+            return Err(line.error(line.end(), "expected ':' after the card number"));
+        };
+
+        let card_number = match header.strip_prefix("Card") {
+            Some(number) => line.parse::<u64>(number.trim(), "a card number")?,
+            None => return Err(line.error(header, "expected 'Card <number>'")),
+        };
+
+        // Cards win copies of the cards after them by number, so they have to be in order.
+        if card_number != cards.len() as u64 + 1 {
+            let message = format!("expected card {}", cards.len() + 1);
+            return Err(line.error(header, message));
+        }
+
+        let Some((winning_numbers, numbers)) = numbers.split_once('|') else {
+            return Err(line.error(line.end(), "expected '|' between the two lists of numbers"));
+        };
+
+        let winning_numbers: Vec<u64> = line.ints(winning_numbers)?;
+        let numbers: Vec<u64> = line.ints(numbers)?;
+
+        cards.push(Card::new(card_number, numbers, winning_numbers));
+    }
+
+    Ok(cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let card = Card::new(
+            1,
+            vec![83, 86, 6, 31, 17, 9, 48, 53],
+            vec![41, 48, 83, 86, 17],
+        );
+
+        assert_eq!(4, card.matches());
+    }
+
+    #[test]
+    fn test_calculate_total_cards() {
+        let cards = [
+            Card::new(1, vec![1, 2], vec![1, 2]),
+            Card::new(2, vec![3], vec![3]),
+            Card::new(3, vec![4], vec![]),
+        ];
+
+        // Card 1 wins a copy of cards 2 and 3, and both copies of card 2 win a copy of card 3.
+        assert_eq!(1 + 2 + 4, calculate_total_cards(&cards));
+    }
+}
@@ -0,0 +1,59 @@
+use aoc_common::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+const SEED_PAIRS: usize = 10;
+
+/// Generates an almanac where every map has `size` ranges, and ten seed pairs that overlap them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1) as u64;
+    let span = size * 1_000_000;
+
+    let seeds: Vec<String> = (0..SEED_PAIRS)
+        .map(|_| {
+            let start = rng.range(0, span - 1);
+            let count = rng.range(1, span - start);
+
+            format!("{} {}", start, count)
+        })
+        .collect();
+
+    let mut output = format!("seeds: {}\n", seeds.join(" "));
+
+    for name in MAPS {
+        // Cut the span into `size` pieces, then move each piece somewhere else in the span. The
+        // source ranges of a map never overlap, but they split up the seed ranges all over.
+        let mut cuts: Vec<u64> = (1..size).map(|_| rng.range(1, span - 1)).collect();
+        cuts.push(0);
+        cuts.push(span);
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        let pieces: Vec<(u64, u64)> = cuts
+            .windows(2)
+            .map(|cut| (cut[0], cut[1] - cut[0]))
+            .collect();
+
+        let mut order: Vec<usize> = (0..pieces.len()).collect();
+        rng.shuffle(&mut order);
+
+        output.push_str(&format!("\n{} map:\n", name));
+
+        let mut dest_start = 0;
+        for index in order {
+            let (source_start, count) = pieces[index];
+            output.push_str(&format!("{} {} {}\n", dest_start, source_start, count));
+            dest_start += count;
+        }
+    }
+
+    output
+}
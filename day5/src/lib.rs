@@ -0,0 +1,310 @@
+pub mod generate;
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use aoc_common::{sections, Error, ParseError, Solution};
+use tracing::{debug, info_span};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(Almanac::parse(input)?)
+    }
+
+    fn part1(almanac: &Self::Input) -> Result<Self::Answer, Error> {
+        almanac
+            .seeds()
+            .iter()
+            .map(|seed| almanac.location_for_seed(*seed))
+            .min()
+            .ok_or_else(|| Error::InvalidInput("the almanac lists no seeds".to_string()))
+    }
+
+    fn part2(almanac: &Self::Input) -> Result<Self::Answer, Error> {
+        if almanac.seeds().len() % 2 != 0 {
+            return Err(Error::InvalidInput(
+                "seeds must come in start and length pairs".to_string(),
+            ));
+        }
+
+        almanac
+            .locations_for_pairs(&almanac.seed_pairs())
+            .iter()
+            .map(|pair| pair.0)
+            .min()
+            .ok_or_else(|| Error::InvalidInput("the almanac lists no seeds".to_string()))
+    }
+}
+
+/// Maps the `count` numbers from `source_start` onto the numbers from `dest_start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Range {
+    pub source_start: i64,
+    pub dest_start: i64,
+    pub count: i64,
+}
+
+/// One "x-to-y map" of the almanac. Numbers not covered by any range map to themselves.
+#[derive(Clone, Debug, Default)]
+pub struct Map {
+    ranges: Vec<Range>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Map { ranges: vec![] }
+    }
+
+    pub fn push_range(&mut self, range: Range) {
+        self.ranges.push(range);
+    }
+
+    pub fn ranges(&self) -> &[Range] {
+        &self.ranges
+    }
+
+    pub fn map_source_to_dest(&self, source: i64) -> i64 {
+        for range in &self.ranges {
+            if (range.source_start..range.source_start + range.count).contains(&source) {
+                return source + (range.dest_start - range.source_start);
+            }
+        }
+
+        source
+    }
+
+    /// Maps `(start, end)` pairs, splitting them where they cross the edge of a range.
+    pub fn map_pairs_to_dest(&self, pairs: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+        // Check for intersections between the source ranges and the input ranges
+
+        // Starting source range:              |----------------------|
+        // Input seed range:                           |-----------------------------|
+        // We only want to map these:                  |--------------|
+        //                                             ^ seed start
+        //                                                            ^ source end
+        // The rest of the input range is checked against the other source ranges, and whatever
+        // is left at the end maps to itself.
+
+        let mut unmapped = pairs;
+        let mut mapped: Vec<(i64, i64)> = vec![];
+
+        for range in &self.ranges {
+            let source_end = range.source_start + range.count;
+            let offset = range.dest_start - range.source_start;
+
+            let mut remaining: Vec<(i64, i64)> = vec![];
+
+            for (start, end) in unmapped {
+                let before = (start, end.min(range.source_start));
+                let inside = (start.max(range.source_start), end.min(source_end));
+                let after = (start.max(source_end), end);
+
+                if before.0 < before.1 {
+                    remaining.push(before);
+                }
+
+                if inside.0 < inside.1 {
+                    mapped.push((inside.0 + offset, inside.1 + offset));
+                }
+
+                if after.0 < after.1 {
+                    remaining.push(after);
+                }
+            }
+
+            unmapped = remaining;
+        }
+
+        mapped.extend(unmapped);
+        mapped
+    }
+}
+
+/// The seeds to plant and the maps that take a seed to its location, in order.
+#[derive(Clone, Debug)]
+pub struct Almanac {
+    seeds: Vec<i64>,
+    mappings: Vec<Map>,
+}
+
+impl Almanac {
+    pub fn new(seeds: Vec<i64>, mappings: Vec<Map>) -> Self {
+        Almanac { seeds, mappings }
+    }
+
+    pub fn seeds(&self) -> &[i64] {
+        &self.seeds
+    }
+
+    pub fn mappings(&self) -> &[Map] {
+        &self.mappings
+    }
+
+    pub fn location_for_seed(&self, seed: i64) -> i64 {
+        let mut val = seed;
+
+        for mapping in &self.mappings {
+            val = mapping.map_source_to_dest(val);
+        }
+
+        val
+    }
+
+    /// Reads the seeds as `(start, end)` pairs, where `end` is exclusive.
+    pub fn seed_pairs(&self) -> Vec<(i64, i64)> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[0] + pair[1]))
+            .collect()
+    }
+
+    pub fn locations_for_pairs(&self, pairs: &[(i64, i64)]) -> Vec<(i64, i64)> {
+        let mut pairs = pairs.to_vec();
+
+        for mapping in &self.mappings {
+            pairs = mapping.map_pairs_to_dest(pairs);
+        }
+
+        pairs
+    }
+
+    /// Reads the seeds and then each map, in the order they are applied.
+    pub fn parse(contents: &str) -> Result<Almanac, ParseError> {
+        let mut seeds: Vec<i64> = vec![];
+
+        let mut mappings: Vec<Map> = vec![];
+
+        // The seeds and every "blank-to-blank" map are separated by blank lines.
+        for section in sections(contents) {
+            let (header, rest) = section.split_first().unwrap();
+
+            // Save the starting seeds
+            if let Some(numbers) = header.text.strip_prefix("seeds:") {
+                seeds = header.ints(numbers)?;
+
+                if let Some(line) = rest.first() {
+                    return Err(line.error(line.text, "expected a blank line after the seeds"));
+                }
+
+                continue;
+            }
+
+            if !header.text.contains("-to-") {
+                return Err(header.error(header.text, "expected '<source>-to-<destination> map:'"));
+            }
+
+            let mut mapping = Map::new();
+
+            for line in rest {
+                let numbers: Vec<i64> = line.ints(line.text)?;
+
+                let [dest_start, source_start, count] = numbers[..] else {
+                    return Err(line.error(
+                        line.text,
+                        "expected a destination start, a source start and a length",
+                    ));
+                };
+
+                let range = Range {
+                    source_start,
+                    dest_start,
+                    count,
+                };
+
+                mapping.push_range(range);
+            }
+
+            mappings.push(mapping);
+        }
+
+        Ok(Almanac::new(seeds, mappings))
+    }
+}
+
+/// Finds the minimum location for the seed pairs by checking every single seed, with one thread
+/// per pair. Only practical for small inputs, but handy to check the range mapping against.
+pub fn min_location_brute_force(almanac: &Almanac) -> Option<i64> {
+    // Loop through every pair.
+    // Check each pair on a new thread.
+    // Thread locks mutex and stores minimum when complete.
+    // Join all threads.
+
+    let min: Arc<Mutex<Option<i64>>> = Arc::new(Mutex::new(None));
+
+    let mut threads = Vec::new();
+
+    for pair in almanac.seed_pairs() {
+        let min = Arc::clone(&min);
+        let almanac = almanac.clone();
+
+        // Created here so that it belongs to the caller's span, not to the new thread.
+        let span = info_span!("seed_pair", start = pair.0, end = pair.1);
+
+        threads.push(thread::spawn(move || {
+            let _span = span.entered();
+            debug!(seeds = pair.1 - pair.0, "checking every seed");
+
+            let mut local_min: Option<i64> = None;
+
+            for seed in pair.0..pair.1 {
+                let val = almanac.location_for_seed(seed);
+
+                local_min = Some(match local_min {
+                    None => val,
+                    Some(min) => std::cmp::min(min, val),
+                });
+            }
+
+            let mut global_min = min.lock().unwrap();
+
+            if let Some(local_min) = local_min {
+                *global_min = Some(match *global_min {
+                    None => local_min,
+                    Some(global_min) => std::cmp::min(global_min, local_min),
+                });
+            }
+
+            debug!(?local_min, "finished");
+        }));
+    }
+
+    threads.into_iter().for_each(|thread| {
+        thread.join().unwrap();
+    });
+
+    let min = *min.lock().unwrap();
+    min
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn test_range_mapping_matches_brute_force() {
+        let almanac = Almanac::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            min_location_brute_force(&almanac),
+            Day5::part2(&almanac).ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Almanac::parse("seeds: 1 2\n\nseed-to-soil map:\n1 2\n").unwrap_err();
+        assert_eq!((4, 1), (error.line, error.column));
+
+        let error = Almanac::parse("seeds: 1 2\n\n1 2 3\n").unwrap_err();
+        assert_eq!((3, 1), (error.line, error.column));
+    }
+}
@@ -0,0 +1,51 @@
+use aoc_common::Rng;
+
+/// Generates races whose times, glued together for part two, make a race of about `size`
+/// milliseconds. Most races can be won in a lot of ways.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let time = rng.range(size as u64 / 2 + 1, size.max(1) as u64);
+    let digits = time.to_string();
+
+    // Split the digits into up to four races. A race can't start with a zero, because the
+    // leading zero would be lost when gluing the races back together.
+    let mut cuts = vec![0];
+    for cut in 1..digits.len() {
+        if cuts.len() < 4 && !digits[cut..].starts_with('0') && rng.chance(1, 2) {
+            cuts.push(cut);
+        }
+    }
+    cuts.push(digits.len());
+
+    let times: Vec<&str> = cuts.windows(2).map(|cut| &digits[cut[0]..cut[1]]).collect();
+
+    // Each record is somewhere below the best distance for its race, so it can be beaten.
+    let distances: Vec<String> = times
+        .iter()
+        .map(|time| {
+            let time: u64 = time.parse().unwrap();
+            let best = (time / 2) * (time - time / 2);
+
+            rng.range(best / 2, best.max(1) - 1).to_string()
+        })
+        .collect();
+
+    let width = times
+        .iter()
+        .map(|time| time.len())
+        .chain(distances.iter().map(String::len))
+        .max()
+        .unwrap();
+
+    let row = |numbers: Vec<String>| {
+        numbers
+            .iter()
+            .map(|number| format!("{:>width$}", number, width = width + 3))
+            .collect::<String>()
+    };
+
+    format!(
+        "Time:    {}\nDistance:{}\n",
+        row(times.iter().map(|time| time.to_string()).collect()),
+        row(distances)
+    )
+}
@@ -0,0 +1,137 @@
+pub mod generate;
+
+use aoc_common::{lines, Error, Line, Solution};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<Race>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        read_races(input)
+    }
+
+    fn part1(races: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(races.iter().map(Race::count_winning_times).product())
+    }
+
+    fn part2(races: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(merge_races(races)?.count_winning_times())
+    }
+}
+
+/// A boat race: how long it lasts and the record distance to beat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Race {
+    time: i64,
+    distance: i64,
+}
+
+impl Race {
+    pub fn new(time: i64, distance: i64) -> Self {
+        Race { time, distance }
+    }
+
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    pub fn distance(&self) -> i64 {
+        self.distance
+    }
+
+    /// Counts how many ways of holding the button beat the record.
+    pub fn count_winning_times(&self) -> i64 {
+        let mut count = 0;
+
+        let total_time = self.time;
+        for time_held in 0..=total_time {
+            if (total_time - time_held) * time_held > self.distance {
+                count += 1;
+            }
+        }
+
+        count
+    }
+}
+
+/// The spaces between the numbers were a lie: glue the digits of every race together into one
+/// long race.
+pub fn merge_races(races: &[Race]) -> Result<Race, Error> {
+    if races.is_empty() {
+        return Err(Error::InvalidInput("there are no races".to_string()));
+    }
+
+    let time: String = races.iter().map(|race| race.time.to_string()).collect();
+    let distance: String = races.iter().map(|race| race.distance.to_string()).collect();
+
+    match (time.parse(), distance.parse()) {
+        (Ok(time), Ok(distance)) => Ok(Race { time, distance }),
+        _ => Err(Error::InvalidInput(format!(
+            "the merged race ({} ms, {} mm) doesn't fit in 64 bits",
+            time, distance
+        ))),
+    }
+}
+
+/// Reads the races from a `Time:` line and a `Distance:` line.
+pub fn read_races(contents: &str) -> Result<Vec<Race>, Error> {
+    let mut times: Option<Vec<i64>> = None;
+    let mut distances: Option<(Line, Vec<i64>)> = None;
+
+    for line in lines(contents) {
+        if let Some(numbers) = line.text.strip_prefix("Time:") {
+            times = Some(line.ints(numbers)?);
+        } else if let Some(numbers) = line.text.strip_prefix("Distance:") {
+            distances = Some((line, line.ints(numbers)?));
+        } else {
+            return Err(line
+                .error(line.text, "expected 'Time:' or 'Distance:'")
+                .into());
+        }
+    }
+
+    let (Some(times), Some((distance_line, distances))) = (times, distances) else {
+        return Err(Error::InvalidInput(
+            "expected a 'Time:' line and a 'Distance:' line".to_string(),
+        ));
+    };
+
+    if times.len() != distances.len() {
+        let message = format!(
+            "found {} distances for {} times",
+            distances.len(),
+            times.len()
+        );
+
+        return Err(distance_line.error(distance_line.text, message).into());
+    }
+
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_winning_times() {
+        assert_eq!(4, Race::new(7, 9).count_winning_times());
+        assert_eq!(8, Race::new(15, 40).count_winning_times());
+        assert_eq!(9, Race::new(30, 200).count_winning_times());
+    }
+
+    #[test]
+    fn test_merge_races() {
+        let races = [Race::new(7, 9), Race::new(15, 40), Race::new(30, 200)];
+
+        assert_eq!(Race::new(71530, 940200), merge_races(&races).unwrap());
+    }
+}
@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Card {
@@ -36,6 +38,14 @@ impl Card {
         }
     }
 
+    /// The value of the card when jacks are jokers, which are the weakest card.
+    pub fn joker_value(&self) -> u32 {
+        match self {
+            Card::Jack => 1,
+            _ => self.value(),
+        }
+    }
+
     pub fn from_char(c: char) -> Option<Self> {
         match c {
             '2' => Some(Self::Two),
@@ -78,16 +88,6 @@ impl Hand {
         Self { cards: *cards }
     }
 
-    pub fn from_str(str: &str) -> Self {
-        let mut cards: [Card; 5] = [Card::Two; 5];
-
-        for (index, c) in str.chars().enumerate() {
-            cards[index] = Card::from_char(c).unwrap();
-        }
-
-        Self { cards: cards }
-    }
-
     pub fn kind(&self) -> HandKind {
         let mut cards = self.cards;
         cards.sort_unstable();
@@ -96,30 +96,12 @@ impl Hand {
             HandKind::FiveOfAKind
         } else if cards.windows(4).any(|w| w.iter().all(|c| c == &w[0])) {
             HandKind::FourOfAKind
-        } else if {
-            let mut first_match = &cards[0];
-
-            cards.windows(3).any(|w| {
-                first_match = &w[0];
-                w.iter().all(|c| c == &w[0])
-            }) && cards
-                .windows(2)
-                .any(|w| w.iter().all(|c| c != first_match && c == &w[0]))
-        } {
+        } else if has_run_and_pair(&cards, 3) {
             // Full house: (for any three cards: all are equal) AND (for any two cards: all are equal)
             HandKind::FullHouse
         } else if cards.windows(3).any(|w| w.iter().all(|c| c == &w[0])) {
             HandKind::ThreeOfAKind
-        } else if {
-            let mut first_match = &cards[0];
-
-            cards.windows(2).any(|w| {
-                first_match = &w[0];
-                w.iter().all(|c| c == &w[0])
-            }) && cards
-                .windows(2)
-                .any(|w| w.iter().all(|c| c != first_match && c == &w[0]))
-        } {
+        } else if has_run_and_pair(&cards, 2) {
             HandKind::TwoPair
         } else if cards.windows(2).any(|w| w.iter().all(|c| c == &w[0])) {
             HandKind::OnePair
@@ -129,6 +111,104 @@ impl Hand {
             HandKind::None
         }
     }
+
+    /// The kind of the hand when jacks are jokers that can pretend to be any other card.
+    pub fn joker_kind(&self) -> HandKind {
+        // Jokers are always best used as more of the card the hand already has the most of.
+        let mut best: Option<(Card, usize)> = None;
+
+        for card in self.cards.iter().filter(|c| **c != Card::Jack) {
+            let count = self.cards.iter().filter(|c| *c == card).count();
+
+            if best.is_none_or(|(_, best_count)| count > best_count) {
+                best = Some((*card, count));
+            }
+        }
+
+        match best {
+            Some((best_card, _)) => {
+                let cards = self
+                    .cards
+                    .map(|c| if c == Card::Jack { best_card } else { c });
+
+                Hand::from_cards(&cards).kind()
+            }
+            // Only jokers
+            None => HandKind::FiveOfAKind,
+        }
+    }
+
+    /// Compares the hands when jacks are jokers.
+    pub fn joker_cmp(&self, other: &Self) -> Ordering {
+        self.joker_kind().cmp(&other.joker_kind()).then_with(|| {
+            let self_values = self.cards.map(|c| c.joker_value());
+            let other_values = other.cards.map(|c| c.joker_value());
+
+            self_values.cmp(&other_values)
+        })
+    }
+
+    /// Returns the stronger of the two hands, or `None` if they are equally strong.
+    pub fn compare_hand<'a>(&'a self, other: &'a Hand) -> Option<&'a Hand> {
+        match self.partial_cmp(other)? {
+            Ordering::Less => Some(other),
+            Ordering::Greater => Some(self),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+/// Checks the sorted cards for a run of `size` equal cards plus a pair of some other card.
+fn has_run_and_pair(cards: &[Card], size: usize) -> bool {
+    let mut first_match = &cards[0];
+
+    cards.windows(size).any(|w| {
+        first_match = &w[0];
+        w.iter().all(|c| c == &w[0])
+    }) && cards
+        .windows(2)
+        .any(|w| w.iter().all(|c| c != first_match && c == &w[0]))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseHandError {
+    /// A hand has exactly five cards, this many were given.
+    WrongLength(usize),
+    /// The character at this byte offset isn't a card.
+    InvalidCard { offset: usize, card: char },
+}
+
+impl fmt::Display for ParseHandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseHandError::WrongLength(length) => {
+                write!(f, "expected a hand of 5 cards, found {}", length)
+            }
+            ParseHandError::InvalidCard { card, .. } => write!(f, "'{}' is not a card", card),
+        }
+    }
+}
+
+impl std::error::Error for ParseHandError {}
+
+impl FromStr for Hand {
+    type Err = ParseHandError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let mut cards: [Card; 5] = [Card::Two; 5];
+
+        let length = str.chars().count();
+        if length != cards.len() {
+            return Err(ParseHandError::WrongLength(length));
+        }
+
+        for (index, (offset, c)) in str.char_indices().enumerate() {
+            cards[index] =
+                Card::from_char(c).ok_or(ParseHandError::InvalidCard { offset, card: c })?;
+        }
+
+        Ok(Self { cards })
+    }
 }
 
 impl PartialOrd for Hand {
@@ -159,6 +239,7 @@ impl PartialOrd for Hand {
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
 
@@ -246,6 +327,37 @@ mod tests {
         assert_eq!(HandKind::HighCard, hand.kind());
     }
 
+    #[test]
+    fn test_joker_kind_four_of_a_kind() {
+        let hand = Hand::from_str("QJJQ2").unwrap();
+        assert_eq!(HandKind::FourOfAKind, hand.joker_kind());
+    }
+
+    #[test]
+    fn test_joker_kind_only_jokers() {
+        let hand = Hand::from_str("JJJJJ").unwrap();
+        assert_eq!(HandKind::FiveOfAKind, hand.joker_kind());
+    }
+
+    #[test]
+    fn test_joker_is_weakest_card() {
+        let hand_1 = Hand::from_str("JKKK2").unwrap();
+        let hand_2 = Hand::from_str("QQQQ2").unwrap();
+
+        assert_eq!(Ordering::Less, hand_1.joker_cmp(&hand_2));
+    }
+
+    #[test]
+    fn test_parse_invalid_card() {
+        assert_eq!(
+            Err(ParseHandError::InvalidCard {
+                offset: 3,
+                card: 'Z'
+            }),
+            Hand::from_str("32TZK")
+        );
+    }
+
     #[test]
     fn test_five_of_a_kind_vs_high_card() {
         let hand_1 = Hand::from_cards(&[Card::Ace; 5]);
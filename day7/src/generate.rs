@@ -0,0 +1,23 @@
+use aoc_common::Rng;
+
+const CARDS: [char; 13] = [
+    '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+];
+
+/// Generates `size` hands, each with a bid of up to 1000.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+
+    for _ in 0..size {
+        // Picking from a few cards at a time makes pairs and full houses as common as in a real
+        // game.
+        let mut cards = CARDS;
+        rng.shuffle(&mut cards);
+        let choices = &cards[..rng.range(1, 13) as usize];
+
+        let hand: String = (0..5).map(|_| *rng.pick(choices)).collect();
+        output.push_str(&format!("{} {}\n", hand, rng.range(1, 1000)));
+    }
+
+    output
+}
@@ -0,0 +1,72 @@
+pub mod camel_cards;
+pub mod generate;
+
+use std::cmp::Ordering;
+
+use aoc_common::{lines, Error, ParseError, Solution};
+use camel_cards::*;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<(Hand, u32)>;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(read_hands(input)?)
+    }
+
+    fn part1(game: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(total_winnings(game, |a, b| a.partial_cmp(b).unwrap()))
+    }
+
+    fn part2(game: &Self::Input) -> Result<Self::Answer, Error> {
+        Ok(total_winnings(game, Hand::joker_cmp))
+    }
+}
+
+/// Reads one hand and its bid per line.
+pub fn read_hands(contents: &str) -> Result<Vec<(Hand, u32)>, ParseError> {
+    let mut game = vec![];
+
+    for line in lines(contents) {
+        let mut words = line.text.split_whitespace();
+
+        let hand_str = words.next().unwrap_or(line.end());
+        let hand = hand_str.parse::<Hand>().map_err(|error| {
+            let fragment = match error {
+                ParseHandError::InvalidCard { offset, card } => {
+                    &hand_str[offset..offset + card.len_utf8()]
+                }
+                ParseHandError::WrongLength(_) => hand_str,
+            };
+
+            line.error(fragment, error.to_string())
+        })?;
+
+        let bid_str = words.next().unwrap_or(line.end());
+        let bid = line.parse::<u32>(bid_str, "a bid")?;
+
+        if let Some(extra) = words.next() {
+            return Err(line.error(extra, "expected only a hand and a bid"));
+        }
+
+        game.push((hand, bid));
+    }
+
+    Ok(game)
+}
+
+/// Ranks the hands from weakest to strongest and adds up every bid multiplied by its rank.
+pub fn total_winnings(game: &[(Hand, u32)], compare: impl Fn(&Hand, &Hand) -> Ordering) -> u64 {
+    let mut game = game.to_vec();
+
+    game.sort_unstable_by(|a, b| compare(&a.0, &b.0));
+
+    game.iter()
+        .enumerate()
+        .map(|(index, hand)| (index + 1) as u64 * hand.1 as u64)
+        .sum()
+}
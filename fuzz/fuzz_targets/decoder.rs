@@ -0,0 +1,64 @@
+//! Feeds arbitrary bytes to the day 1 decoder, in every mode and through the stream decoder, to
+//! check that nothing in it panics.
+//!
+//! Run from this directory with `cargo +nightly fuzz run decoder`.
+
+#![no_main]
+
+use std::path::Path;
+
+use day1::{CalibrationDecoder, Mode, NoDigits, StreamDecoder};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // The first byte picks the options, the rest is the document.
+    let Some((&options, bytes)) = data.split_first() else {
+        return;
+    };
+
+    let mode = match options % 3 {
+        0 => Mode::DigitsOnly,
+        1 => Mode::DigitsAndWords,
+        _ => Mode::Compound,
+    };
+
+    let mut decoder = CalibrationDecoder::new(mode);
+    if options & 0x04 != 0 {
+        decoder = decoder.with_full_width_digits();
+    }
+
+    let policy = match (options >> 3) % 3 {
+        0 => NoDigits::Zero,
+        1 => NoDigits::Skip,
+        _ => NoDigits::Error,
+    };
+
+    let chunk_size = usize::from(options >> 5) + 1;
+    let streamed = StreamDecoder::new(policy)
+        .with_chunk_size(chunk_size)
+        .with_threads(2)
+        .decode(&decoder, bytes, Path::new("fuzz"));
+
+    let Ok(input) = std::str::from_utf8(bytes) else {
+        assert!(streamed.is_err());
+        return;
+    };
+
+    for line in input.lines() {
+        if let Some((first, last)) = decoder.first_and_last(line) {
+            assert!(first.start <= last.start && first.end <= last.end);
+            assert!(line.is_char_boundary(first.start) && line.is_char_boundary(last.end));
+        }
+
+        decoder.tokens(line);
+    }
+
+    let explained = day1::explain::explain(&decoder, input, policy);
+    assert_eq!(decoder.decode(input, policy).is_ok(), explained.is_ok());
+
+    assert_eq!(
+        decoder.decode(input, policy).ok(),
+        streamed.ok(),
+        "the stream decoder disagrees"
+    );
+});